@@ -0,0 +1,48 @@
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/input");
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+
+    // Inputs live in a folder per year, e.g. src/input/2020/day07.txt.
+    let mut inputs = fs::read_dir("src/input")
+        .expect("Failed to read src/input")
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let year = entry.file_name().into_string().ok()?.parse::<u16>().ok()?;
+            let days = fs::read_dir(entry.path()).ok()?.filter_map(move |entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                let day = name
+                    .strip_prefix("day")?
+                    .strip_suffix(".txt")?
+                    .parse::<u8>()
+                    .ok()?;
+                Some((year, day))
+            });
+            Some(days)
+        })
+        .flatten()
+        .collect::<Vec<(u16, u8)>>();
+    inputs.sort_unstable();
+
+    let arms = inputs
+        .iter()
+        .map(|(year, day)| {
+            format!(
+                "        ({}, {}) => Some(include_str!(concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/src/input/{}/day{:02}.txt\"))),\n",
+                year, day, year, day
+            )
+        })
+        .collect::<String>();
+
+    let code = format!(
+        "fn embedded_input(year: u16, day: u8) -> Option<&'static str> {{\n    match (year, day) {{\n{}        _ => None,\n    }}\n}}\n",
+        arms
+    );
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    fs::write(out, code).expect("Failed to write embedded inputs");
+}
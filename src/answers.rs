@@ -0,0 +1,150 @@
+use anyhow::{bail, Context, Result};
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::solution::{Part, PartResult};
+
+/// The default location of the recorded answers, relative to the crate root.
+pub const DEFAULT_ANSWERS_PATH: &str = "./answers.txt";
+
+/// Known-good answers, keyed by year, day and part.
+///
+/// The file format is one `<year> <day> <part> <answer>` entry per line. Blank lines and lines
+/// starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: HashMap<(u16, u8, Part), String>,
+}
+
+/// How a computed answer compares to the recorded one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read answers file '{}'", path.display()))?;
+
+        Answers::parse(&text).with_context(|| format!("Invalid answers file '{}'", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Answers> {
+        let mut expected = HashMap::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.splitn(4, char::is_whitespace);
+            let (year, day, part, answer) =
+                match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                    (Some(year), Some(day), Some(part), Some(answer)) => {
+                        (year, day, part, answer.trim())
+                    }
+                    _ => bail!(
+                        "Line {} should be '<year> <day> <part> <answer>': '{}'",
+                        index + 1,
+                        line
+                    ),
+                };
+
+            let year = year
+                .parse::<u16>()
+                .with_context(|| format!("Invalid year on line {}: '{}'", index + 1, year))?;
+            let day = day
+                .parse::<u8>()
+                .with_context(|| format!("Invalid day on line {}: '{}'", index + 1, day))?;
+            let part = part
+                .parse::<Part>()
+                .with_context(|| format!("Invalid part on line {}", index + 1))?;
+
+            expected.insert((year, day, part), answer.to_owned());
+        }
+
+        Ok(Answers { expected })
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&str> {
+        self.expected.get(&(year, day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, result: &PartResult) -> Verdict {
+        match self.get(result.year, result.day, result.part) {
+            Some(expected) if expected == result.answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_owned(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn result(year: u16, day: u8, part: Part, answer: &str) -> PartResult {
+        PartResult {
+            year,
+            day,
+            part,
+            answer: answer.to_owned(),
+            description: String::new(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn parses_and_checks() {
+        let answers =
+            Answers::parse("# Comment\n\n2020 01 1 514579\n2020 1 2  two words \n2019 1 1 3\n")
+                .unwrap();
+
+        assert_eq!(answers.get(2020, 1, Part::One), Some("514579"));
+        assert_eq!(answers.get(2020, 1, Part::Two), Some("two words"));
+        assert_eq!(answers.get(2020, 2, Part::One), None);
+        assert_eq!(answers.get(2019, 1, Part::One), Some("3"));
+
+        assert_eq!(
+            answers.check(&result(2020, 1, Part::One, "514579")),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(&result(2020, 1, Part::Two, "42")),
+            Verdict::Fail {
+                expected: "two words".to_owned()
+            }
+        );
+        assert_eq!(
+            answers.check(&result(2020, 3, Part::One, "7")),
+            Verdict::Missing
+        );
+        assert_eq!(
+            answers.check(&result(2019, 1, Part::Two, "3")),
+            Verdict::Missing
+        );
+    }
+
+    #[test]
+    fn rejects_bad_lines() {
+        let error = Answers::parse("2020 1 1 5\n2020 1 1\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Line 2 should be '<year> <day> <part> <answer>': '2020 1 1'"
+        );
+
+        let error = Answers::parse("2020 x 1 5\n").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid day on line 1: 'x'");
+
+        let error = Answers::parse("x 1 1 5\n").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid year on line 1: 'x'");
+
+        assert!(Answers::parse("2020 1 3 5\n").is_err());
+    }
+}
@@ -0,0 +1,339 @@
+use anyhow::{anyhow, bail, Context, Error, Result};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Write as _},
+    fs,
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::solution::{self, Solution};
+
+/// The default location of the stored benchmark baseline, relative to the crate root.
+pub const DEFAULT_BASELINE_PATH: &str = "./bench-baseline.txt";
+
+/// A separately timed step of a day.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        })
+    }
+}
+
+impl FromStr for Phase {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Part1),
+            "part2" => Ok(Phase::Part2),
+            _ => Err(anyhow!("Phase must be parse, part1 or part2, got '{}'", s)),
+        }
+    }
+}
+
+/// How many times to run each day.
+#[derive(Debug, Copy, Clone)]
+pub struct BenchConfig {
+    /// Runs made before measuring, to warm caches and the allocator. These aren't recorded.
+    pub warmup: usize,
+    /// Measured runs.
+    pub runs: usize,
+}
+
+/// Every measured sample of one phase of a day, sorted from fastest to slowest.
+#[derive(Debug, Clone)]
+pub struct Timing {
+    pub year: u16,
+    pub day: u8,
+    pub phase: Phase,
+    samples: Vec<Duration>,
+}
+
+impl Timing {
+    fn new(year: u16, day: u8, phase: Phase, mut samples: Vec<Duration>) -> Timing {
+        samples.sort_unstable();
+
+        Timing {
+            year,
+            day,
+            phase,
+            samples,
+        }
+    }
+
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+
+    pub fn median(&self) -> Duration {
+        let mid = self.samples.len() / 2;
+
+        if self.samples.len().is_multiple_of(2) {
+            (self.samples[mid - 1] + self.samples[mid]) / 2
+        } else {
+            self.samples[mid]
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+
+    /// The gap between the slowest and fastest samples as a fraction of the median.
+    pub fn spread(&self) -> f64 {
+        ratio(self.max() - self.min(), self.median())
+    }
+}
+
+/// Times parsing and both parts of `S` separately, `config.runs` times each after the warm-up.
+///
+/// Each run goes through `solution::run_with`, so it's held to the same budget as `aoc run`.
+pub fn bench<S: Solution>(config: &BenchConfig) -> Result<Vec<Timing>> {
+    if config.runs == 0 {
+        bail!("At least one measured run is needed");
+    }
+
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+
+    for run in 0..config.warmup + config.runs {
+        let mut elapsed = [Duration::ZERO; 3];
+        solution::run_with::<S>(None, |phase, f| {
+            let start = Instant::now();
+            f();
+            elapsed[phase as usize] = start.elapsed();
+        })?;
+
+        if run >= config.warmup {
+            for (samples, elapsed) in samples.iter_mut().zip(elapsed) {
+                samples.push(elapsed);
+            }
+        }
+    }
+
+    let [parse, part1, part2] = samples;
+
+    Ok(vec![
+        Timing::new(S::YEAR, S::DAY, Phase::Parse, parse),
+        Timing::new(S::YEAR, S::DAY, Phase::Part1, part1),
+        Timing::new(S::YEAR, S::DAY, Phase::Part2, part2),
+    ])
+}
+
+/// Stored median timings to compare later runs against.
+///
+/// The file format is one `<year> <day> <phase> <nanoseconds>` entry per line. Blank lines and
+/// lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: HashMap<(u16, u8, Phase), Duration>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline file '{}'", path.display()))?;
+
+        Baseline::parse(&text)
+            .with_context(|| format!("Invalid baseline file '{}'", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Baseline> {
+        let mut medians = HashMap::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let (year, day, phase, nanos) = match fields.as_slice() {
+                [year, day, phase, nanos] => (year, day, phase, nanos),
+                _ => bail!(
+                    "Line {} should be '<year> <day> <phase> <nanoseconds>': '{}'",
+                    index + 1,
+                    line
+                ),
+            };
+
+            let year = year
+                .parse::<u16>()
+                .with_context(|| format!("Invalid year on line {}: '{}'", index + 1, year))?;
+            let day = day
+                .parse::<u8>()
+                .with_context(|| format!("Invalid day on line {}: '{}'", index + 1, day))?;
+            let phase = phase
+                .parse::<Phase>()
+                .with_context(|| format!("Invalid phase on line {}", index + 1))?;
+            let nanos = nanos
+                .parse::<u64>()
+                .with_context(|| format!("Invalid time on line {}: '{}'", index + 1, nanos))?;
+
+            medians.insert((year, day, phase), Duration::from_nanos(nanos));
+        }
+
+        Ok(Baseline { medians })
+    }
+
+    /// Writes the baseline to `path`, replacing whatever was there.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut entries = self.medians.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(key, _)| **key);
+
+        let mut text =
+            String::from("# Median benchmark timings: <year> <day> <phase> <nanoseconds>\n");
+        for ((year, day, phase), median) in entries {
+            writeln!(text, "{} {:02} {} {}", year, day, phase, median.as_nanos()).unwrap();
+        }
+
+        fs::write(path, text)
+            .with_context(|| format!("Failed to write baseline file '{}'", path.display()))
+    }
+
+    /// Records the median of `timing`, replacing any earlier entry for the same day and phase.
+    pub fn record(&mut self, timing: &Timing) {
+        self.medians
+            .insert((timing.year, timing.day, timing.phase), timing.median());
+    }
+
+    pub fn get(&self, year: u16, day: u8, phase: Phase) -> Option<Duration> {
+        self.medians.get(&(year, day, phase)).copied()
+    }
+
+    /// How `timing` compares to the stored median, as a fraction: `0.1` is 10% slower and `-0.1`
+    /// is 10% faster. `None` if nothing was stored for that day and phase.
+    pub fn change(&self, timing: &Timing) -> Option<f64> {
+        self.get(timing.year, timing.day, timing.phase)
+            .map(|baseline| ratio(timing.median(), baseline) - 1.0)
+    }
+}
+
+/// Formats a duration with three significant figures in the most readable unit.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+
+    let (value, unit) = if nanos >= 1e9 {
+        (nanos / 1e9, "s")
+    } else if nanos >= 1e6 {
+        (nanos / 1e6, "ms")
+    } else if nanos >= 1e3 {
+        (nanos / 1e3, "µs")
+    } else {
+        (nanos, "ns")
+    };
+
+    let decimals = if value >= 100.0 {
+        0
+    } else if value >= 10.0 {
+        1
+    } else {
+        2
+    };
+
+    format!("{:.*}{}", decimals, value, unit)
+}
+
+fn ratio(numerator: Duration, denominator: Duration) -> f64 {
+    if denominator.is_zero() {
+        return 0.0;
+    }
+
+    numerator.as_secs_f64() / denominator.as_secs_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn timing(day: u8, phase: Phase, millis: &[u64]) -> Timing {
+        Timing::new(
+            2020,
+            day,
+            phase,
+            millis.iter().map(|ms| Duration::from_millis(*ms)).collect(),
+        )
+    }
+
+    #[test]
+    fn takes_median() {
+        assert_eq!(
+            timing(1, Phase::Parse, &[30, 10, 20]).median(),
+            Duration::from_millis(20)
+        );
+        assert_eq!(
+            timing(1, Phase::Parse, &[40, 10, 20, 30]).median(),
+            Duration::from_millis(25)
+        );
+    }
+
+    #[test]
+    fn compares_to_baseline() {
+        let baseline = Baseline::parse(
+            "# Comment\n\n2020 01 part1 100000000\n2020 1 parse 50000000\n2019 1 part2 5\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            baseline.get(2020, 1, Phase::Part1),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(baseline.get(2020, 1, Phase::Part2), None);
+        assert_eq!(baseline.get(2019, 1, Phase::Part1), None);
+
+        let change = baseline.change(&timing(1, Phase::Part1, &[110])).unwrap();
+        assert!((change - 0.1).abs() < 1e-9);
+        let change = baseline.change(&timing(1, Phase::Parse, &[25])).unwrap();
+        assert!((change + 0.5).abs() < 1e-9);
+        assert_eq!(baseline.change(&timing(2, Phase::Part1, &[10])), None);
+    }
+
+    #[test]
+    fn rejects_bad_lines() {
+        let error = Baseline::parse("2020 01 part1\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Line 1 should be '<year> <day> <phase> <nanoseconds>': '2020 01 part1'"
+        );
+
+        assert!(Baseline::parse("01 part1 5\n").is_err());
+        assert!(Baseline::parse("2020 01 part3 5\n").is_err());
+        assert!(Baseline::parse("2020 01 part1 fast\n").is_err());
+    }
+
+    #[test]
+    fn saves_and_loads() {
+        let mut baseline = Baseline::default();
+        baseline.record(&timing(2, Phase::Part2, &[7]));
+        baseline.record(&timing(1, Phase::Parse, &[3]));
+
+        let path = env::temp_dir().join(format!("aoc-baseline-{}.txt", std::process::id()));
+        baseline.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(text.ends_with("2020 01 parse 3000000\n2020 02 part2 7000000\n"));
+        assert_eq!(
+            loaded.get(2020, 2, Phase::Part2),
+            Some(Duration::from_millis(7))
+        );
+    }
+}
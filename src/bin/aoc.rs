@@ -0,0 +1,756 @@
+use anyhow::{bail, Context, Result};
+use aoc_2020::{
+    answers::{Answers, Verdict, DEFAULT_ANSWERS_PATH},
+    bench::{format_duration, Baseline, BenchConfig, DEFAULT_BASELINE_PATH},
+    budget::{set_budget, Budget},
+    client::{self, Client, Fetched},
+    generate, input_path,
+    memory::{self, format_bytes, CountingAllocator, PhaseAllocations},
+    runner::{self, DayReport, Status},
+    scaffold, set_input_source,
+    solution::{self, Part, PartResult, DEFAULT_YEAR},
+    submit::{Outcome, Submission, SubmissionLog, DEFAULT_LOG_PATH},
+    watch::{self, DayWatcher},
+    InputSource,
+};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use log::LevelFilter;
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+// Only counts once `--allocs` enables it.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs the Advent of Code solutions.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// The Advent of Code year that days belong to.
+    #[arg(long, global = true, default_value_t = DEFAULT_YEAR)]
+    year: u16,
+
+    /// Log what the solutions are doing to stderr: `-v` for debug messages, `-vv` for trace.
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a single day, or every day with `--all`.
+    Run {
+        /// The day to run.
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+
+        /// Only run the given part (1 or 2).
+        #[arg(long)]
+        part: Option<Part>,
+
+        /// Run every implemented day in order.
+        #[arg(long)]
+        all: bool,
+
+        /// With `--all`, run the days concurrently and print a summary table at the end. A day
+        /// that fails or panics is reported without stopping the others.
+        #[arg(long, requires = "all")]
+        parallel: bool,
+
+        /// How many days to run at once with `--parallel`. Defaults to the number of CPUs.
+        #[arg(long, requires = "parallel")]
+        jobs: Option<usize>,
+
+        /// Read input from a file, a directory of `dayNN.txt` files, or `-` for stdin.
+        #[arg(long, value_name = "PATH")]
+        input: Option<InputSource>,
+
+        /// How to print the answers.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Count the allocations, bytes allocated and peak live bytes of parsing and each part,
+        /// and print them after the answers.
+        #[arg(long, conflicts_with = "parallel")]
+        allocs: bool,
+
+        #[command(flatten)]
+        budget: BudgetArgs,
+    },
+    /// Run every day and check the answers against the recorded ones.
+    Verify {
+        /// The file of recorded `<year> <day> <part> <answer>` lines.
+        #[arg(long, value_name = "PATH", default_value = DEFAULT_ANSWERS_PATH)]
+        answers: PathBuf,
+    },
+    /// Time parsing and each part separately, and compare the medians to a stored baseline.
+    ///
+    /// Build with `--release` for meaningful numbers.
+    Bench {
+        /// The day to benchmark. Every day is benchmarked if this is left out.
+        day: Option<u8>,
+
+        /// Unmeasured runs to make before timing starts.
+        #[arg(long, default_value_t = 1)]
+        warmup: usize,
+
+        /// Measured runs per day.
+        #[arg(long, default_value_t = 5)]
+        runs: usize,
+
+        /// The file of stored `<year> <day> <phase> <nanoseconds>` medians.
+        #[arg(long, value_name = "PATH", default_value = DEFAULT_BASELINE_PATH)]
+        baseline: PathBuf,
+
+        /// Store this run's medians in the baseline file instead of failing on regressions.
+        #[arg(long)]
+        save: bool,
+
+        /// How much slower than the baseline, in percent, a phase can be before it's flagged.
+        #[arg(long, value_name = "PERCENT", default_value_t = 10.0)]
+        threshold: f64,
+
+        #[command(flatten)]
+        budget: BudgetArgs,
+    },
+    /// Download a day's puzzle input, unless it's already on disk.
+    ///
+    /// The session token is read from `--session-file`, then `AOC_SESSION`, then
+    /// `./.aoc-session`. Set `AOC_BASE_URL` to use a different server.
+    Fetch {
+        /// The day to download.
+        day: u8,
+
+        /// A file holding the session token.
+        #[arg(long, value_name = "PATH")]
+        session_file: Option<PathBuf>,
+    },
+    /// Run one part of a day and submit its answer.
+    ///
+    /// Every submission is recorded locally, and answers that were already submitted or that
+    /// would break a cooldown the site asked for are refused without contacting it.
+    Submit {
+        /// The day to submit.
+        day: u8,
+
+        /// The part to submit (1 or 2).
+        part: Part,
+
+        /// A file holding the session token.
+        #[arg(long, value_name = "PATH")]
+        session_file: Option<PathBuf>,
+
+        /// The local record of submitted answers.
+        #[arg(long, value_name = "PATH", default_value = DEFAULT_LOG_PATH)]
+        log: PathBuf,
+    },
+    /// Rebuild and rerun a day whenever its source, shared code or input changes.
+    Watch {
+        /// The day to watch.
+        day: u8,
+    },
+    /// Run one day on every file in a directory, such as other people's puzzle inputs, and print
+    /// the answers and timings for each.
+    ///
+    /// Inputs that make the day fail, panic or run past the timeout are flagged.
+    Compare {
+        /// The day to run.
+        day: u8,
+
+        /// The directory of inputs.
+        dir: PathBuf,
+
+        /// Only run the given part (1 or 2).
+        #[arg(long)]
+        part: Option<Part>,
+
+        /// Seconds each input can run for before it's flagged as not terminating.
+        #[arg(long, value_name = "SECONDS", default_value_t = 10.0)]
+        timeout: f64,
+    },
+    /// Create the module, binary, empty input file and example test stub for a new day.
+    New {
+        /// The day to create.
+        day: u8,
+    },
+    /// Write a synthetic input for a day, of any size, for testing and benchmarking.
+    ///
+    /// The same size and seed always give the same input.
+    Generate {
+        /// The day to generate an input for.
+        day: u8,
+
+        /// Roughly how many lines or records to write.
+        #[arg(long)]
+        size: usize,
+
+        /// The random seed.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Write the input to a file instead of stdout.
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Limits that stop a day which runs away, shared by the commands that run days.
+#[derive(Args)]
+struct BudgetArgs {
+    /// Fail a day once any of its solver loops has run this many iterations.
+    #[arg(long, value_name = "STEPS")]
+    max_steps: Option<u64>,
+
+    /// Fail a day once it has run for this many seconds, parsing included.
+    #[arg(long, value_name = "SECONDS")]
+    time_limit: Option<f64>,
+}
+
+impl BudgetArgs {
+    /// Makes these limits the budget for every run in this process.
+    fn apply(&self) -> Result<()> {
+        set_budget(Budget {
+            steps: self.max_steps,
+            time: self.time_limit.map(seconds).transpose()?,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, ValueEnum)]
+enum Format {
+    /// A sentence per part, as written by each day.
+    Text,
+    /// One `{year, day, part, answer, elapsed}` object per line, with `answer` as a string and
+    /// `elapsed` in seconds.
+    Json,
+    /// One tab-separated `<year> <day> <part> <answer>` line per part, with the answer escaped.
+    Tsv,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let year = cli.year;
+    init_logging(cli.verbose);
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            all,
+            parallel,
+            jobs,
+            input,
+            format,
+            allocs,
+            budget,
+        } => {
+            if allocs {
+                memory::enable()?;
+            }
+
+            budget.apply()?;
+
+            if let Some(input) = input {
+                if all && !matches!(input, InputSource::Dir(_)) {
+                    bail!("--input must be a directory when running every day");
+                }
+
+                set_input_source(input);
+            }
+
+            if parallel {
+                if format != Format::Text {
+                    bail!("--parallel only prints a text summary");
+                }
+
+                let reports = runner::run_parallel(
+                    &solution::days(year).collect::<Vec<_>>(),
+                    part,
+                    jobs.unwrap_or_else(runner::default_jobs),
+                );
+                print_summary(&reports);
+
+                let failed = reports.iter().filter(|report| !report.is_ok()).count();
+                if failed > 0 {
+                    bail!("{} day(s) failed", failed);
+                }
+            } else {
+                let days = match day {
+                    Some(day) => vec![solution::find(year, day)?],
+                    None => solution::days(year).collect(),
+                };
+                let mut phases = Vec::new();
+
+                for reg in days {
+                    if all && format == Format::Text {
+                        println!("Day {:02}", reg.day);
+                    }
+
+                    if allocs {
+                        let profile = (reg.profile)(part)?;
+                        print_results(&profile.results, format);
+                        phases.extend(profile.phases);
+                    } else {
+                        print_results(&(reg.run)(part)?, format);
+                    }
+                }
+
+                if allocs {
+                    print_allocations(&phases, format);
+                }
+            }
+
+            Ok(())
+        }
+        Command::Verify { answers } => verify(year, &Answers::load(&answers)?),
+        Command::Bench {
+            day,
+            warmup,
+            runs,
+            baseline,
+            save,
+            threshold,
+            budget,
+        } => {
+            budget.apply()?;
+
+            bench(
+                year,
+                day,
+                &BenchConfig { warmup, runs },
+                &baseline,
+                save,
+                threshold / 100.0,
+            )
+        }
+        Command::Fetch { day, session_file } => {
+            let path = input_path(year, day);
+            if client::has_input(&path) {
+                println!("Already have {}", path.display());
+                return Ok(());
+            }
+
+            let client = Client::from_env(session_file.as_deref())?;
+            match client::fetch_input(&client, year, day, &path)? {
+                Fetched::Downloaded(path) => println!("Wrote {}", path.display()),
+                Fetched::AlreadyPresent(path) => println!("Already have {}", path.display()),
+            }
+
+            Ok(())
+        }
+        Command::Submit {
+            day,
+            part,
+            session_file,
+            log,
+        } => submit(year, day, part, session_file.as_deref(), &log),
+        Command::Watch { day } => {
+            let watcher = DayWatcher::new(year, day)?;
+            let mut previous = rerun(year, day, &[]);
+
+            loop {
+                let changed = watcher
+                    .wait()?
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>();
+                println!("\nChanged: {}", changed.join(", "));
+
+                previous = rerun(year, day, &previous);
+            }
+        }
+        Command::Compare {
+            day,
+            dir,
+            part,
+            timeout,
+        } => {
+            let timeout = seconds(timeout)?;
+
+            let paths = runner::input_files(&dir)?;
+            if paths.is_empty() {
+                bail!("There are no inputs in '{}'", dir.display());
+            }
+
+            let reports = runner::run_inputs(solution::find(year, day)?, &paths, part, timeout);
+            let rows = reports
+                .iter()
+                .map(|input| {
+                    let name = input.path.file_name().unwrap_or(input.path.as_os_str());
+                    (name.to_string_lossy().into_owned(), &input.report)
+                })
+                .collect::<Vec<_>>();
+            print_reports("Input", &rows);
+
+            let failed = reports.iter().filter(|input| !input.report.is_ok()).count();
+            println!("{} input(s) ok, {} failed", reports.len() - failed, failed);
+            if failed > 0 {
+                bail!("Day {} failed on {} input(s)", day, failed);
+            }
+
+            Ok(())
+        }
+        Command::New { day } => {
+            for path in scaffold::new_day(Path::new("."), year, day)? {
+                println!("Wrote {}", path.display());
+            }
+
+            Ok(())
+        }
+        Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        } => {
+            let input = generate::generate(year, day, size, seed)?;
+
+            match output {
+                Some(path) => fs::write(&path, input)
+                    .with_context(|| format!("Failed to write {}", path.display())),
+                None => {
+                    print!("{}", input);
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+fn bench(
+    year: u16,
+    day: Option<u8>,
+    config: &BenchConfig,
+    baseline_path: &Path,
+    save: bool,
+    threshold: f64,
+) -> Result<()> {
+    let mut baseline = if baseline_path.exists() {
+        Baseline::load(baseline_path)?
+    } else {
+        Baseline::default()
+    };
+
+    let days = match day {
+        Some(day) => vec![solution::find(year, day)?],
+        None => solution::days(year).collect(),
+    };
+
+    println!(
+        "{:<4}{:<7}{:>10}{:>10}{:>10}{:>8}{:>10}{:>9}",
+        "Day", "Phase", "Median", "Min", "Max", "Spread", "Baseline", "Change"
+    );
+
+    let mut timings = Vec::new();
+    let mut regressions = 0;
+
+    for reg in days {
+        for timing in (reg.bench)(config)? {
+            let change = baseline.change(&timing);
+            let regressed = change.is_some_and(|change| change > threshold);
+
+            println!(
+                "{:<4}{:<7}{:>10}{:>10}{:>10}{:>7.0}%{:>10}{:>9}{}",
+                format!("{:02}", timing.day),
+                timing.phase,
+                format_duration(timing.median()),
+                format_duration(timing.min()),
+                format_duration(timing.max()),
+                timing.spread() * 100.0,
+                baseline
+                    .get(timing.year, timing.day, timing.phase)
+                    .map_or_else(|| "-".to_owned(), format_duration),
+                change.map_or_else(|| "-".to_owned(), |c| format!("{:+.1}%", c * 100.0)),
+                if regressed { "  REGRESSION" } else { "" }
+            );
+
+            if regressed {
+                regressions += 1;
+            }
+            timings.push(timing);
+        }
+    }
+
+    if save {
+        for timing in &timings {
+            baseline.record(timing);
+        }
+        baseline.save(baseline_path)?;
+        println!("Saved baseline to '{}'", baseline_path.display());
+    } else if regressions > 0 {
+        bail!(
+            "{} phase(s) are more than {}% slower than the baseline",
+            regressions,
+            threshold * 100.0
+        );
+    }
+
+    Ok(())
+}
+
+fn submit(
+    year: u16,
+    day: u8,
+    part: Part,
+    session_file: Option<&Path>,
+    log_path: &Path,
+) -> Result<()> {
+    let answer = match solution::run_day(year, day, Some(part))?.pop() {
+        Some(result) => result.answer,
+        None => bail!("Day {} part {} produced no answer", day, part),
+    };
+
+    let mut log = SubmissionLog::load(log_path)?;
+    if let Some(reason) = log.check(year, day, part, &answer, SystemTime::now()) {
+        bail!("Not submitting {}: {}", answer, reason);
+    }
+
+    let client = Client::from_env(session_file)?;
+    println!(
+        "Submitting {} for {} day {} part {}",
+        answer, year, day, part
+    );
+
+    let outcome = client.submit(year, day, part, &answer)?;
+    log.record(
+        log_path,
+        Submission::new(year, day, part, &answer, &outcome, SystemTime::now()),
+    )?;
+
+    println!("{}", outcome);
+    if outcome != Outcome::Correct {
+        bail!("The answer was not accepted");
+    }
+
+    Ok(())
+}
+
+/// Runs the day again and prints each answer next to the one from the run before. A failed run
+/// keeps the previous answers to compare against next time.
+fn rerun(year: u16, day: u8, previous: &[(Part, String)]) -> Vec<(Part, String)> {
+    let answers = match watch::rebuild_and_run(year, day) {
+        Ok(answers) => answers,
+        Err(e) => {
+            println!("{:#}", e);
+            return previous.to_vec();
+        }
+    };
+
+    for (part, answer) in &answers {
+        match previous.iter().find(|(other, _)| other == part) {
+            Some((_, old)) if old == answer => println!("Part {}: {} (unchanged)", part, answer),
+            Some((_, old)) => println!("Part {}: {} (was {})", part, answer, old),
+            None => println!("Part {}: {}", part, answer),
+        }
+    }
+
+    answers
+}
+
+fn verify(year: u16, answers: &Answers) -> Result<()> {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for reg in solution::days(year) {
+        let results = match (reg.run)(None) {
+            Ok(results) => results,
+            Err(e) => {
+                println!("Day {:02}: error: {:#}", reg.day, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for result in results {
+            let verdict = answers.check(&result);
+            print!("Day {:02} part {}: ", result.day, result.part);
+
+            match verdict {
+                Verdict::Pass => {
+                    println!("pass");
+                    passed += 1;
+                }
+                Verdict::Fail { expected } => {
+                    println!("FAIL (expected {}, got {})", expected, result.answer);
+                    failed += 1;
+                }
+                Verdict::Missing => {
+                    println!("missing (got {})", result.answer);
+                    missing += 1;
+                }
+            }
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    if failed > 0 {
+        bail!("{} answer(s) did not match", failed);
+    }
+
+    Ok(())
+}
+
+fn seconds(seconds: f64) -> Result<Duration> {
+    Duration::try_from_secs_f64(seconds).context("Expected a positive number of seconds")
+}
+
+/// Sends log messages to stderr so they never mix with the answers on stdout. `RUST_LOG` overrides
+/// the level picked by `-v`.
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .format_timestamp(None)
+        .target(env_logger::Target::Stderr)
+        .init();
+}
+
+/// Prints a row per part, or a single row for a day that didn't finish.
+fn print_summary(reports: &[DayReport]) {
+    let rows = reports
+        .iter()
+        .map(|report| (format!("{:02}", report.day), report))
+        .collect::<Vec<_>>();
+    print_reports("Day", &rows);
+
+    let ok = reports.iter().filter(|report| report.is_ok()).count();
+    println!("{} day(s) ok, {} failed", ok, reports.len() - ok);
+}
+
+/// Prints a row per part of each report, or a single row for one that didn't finish. The first
+/// column holds each report's label, under `heading`.
+fn print_reports(heading: &str, reports: &[(String, &DayReport)]) {
+    let width = reports
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .chain([heading.len()])
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    println!(
+        "{:<width$}{:<6}{:<20}{:>10}  Status",
+        heading,
+        "Part",
+        "Answer",
+        "Time",
+        width = width
+    );
+
+    for (label, report) in reports {
+        let row = |part: &dyn Display, answer: &str, elapsed: Duration, status: &dyn Display| {
+            println!(
+                "{:<width$}{:<6}{:<20}{:>10}  {}",
+                label,
+                part.to_string(),
+                answer,
+                format_duration(elapsed),
+                status,
+                width = width
+            )
+        };
+
+        match &report.status {
+            Status::Ok(results) => {
+                for result in results {
+                    row(&result.part, &result.answer, result.elapsed, &"ok");
+                }
+            }
+            Status::Error(e) => row(&"-", "-", report.elapsed, &format!("error: {:#}", e)),
+            Status::Panic(message) => {
+                row(&"-", "-", report.elapsed, &format!("panic: {}", message))
+            }
+            Status::Timeout => row(&"-", "-", report.elapsed, &"timed out"),
+        }
+    }
+}
+
+fn print_allocations(phases: &[PhaseAllocations], format: Format) {
+    if format == Format::Text {
+        println!(
+            "\n{:<4}{:<7}{:>10}{:>12}{:>12}",
+            "Day", "Phase", "Allocs", "Allocated", "Peak"
+        );
+    }
+
+    for phase in phases {
+        let allocations = phase.allocations;
+
+        match format {
+            Format::Text => println!(
+                "{:<4}{:<7}{:>10}{:>12}{:>12}",
+                format!("{:02}", phase.day),
+                phase.phase,
+                allocations.count,
+                format_bytes(allocations.bytes),
+                format_bytes(allocations.peak)
+            ),
+            Format::Json => println!(
+                "{{\"year\":{},\"day\":{},\"phase\":\"{}\",\"allocations\":{},\"bytes\":{},\"peak\":{}}}",
+                phase.year,
+                phase.day,
+                phase.phase,
+                allocations.count,
+                allocations.bytes,
+                allocations.peak
+            ),
+            Format::Tsv => println!(
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                phase.year,
+                phase.day,
+                phase.phase,
+                allocations.count,
+                allocations.bytes,
+                allocations.peak
+            ),
+        }
+    }
+}
+
+fn print_results(results: &[PartResult], format: Format) {
+    for result in results {
+        match format {
+            Format::Text => println!("{}", result.description),
+            Format::Json => println!(
+                "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"elapsed\":{}}}",
+                result.year,
+                result.day,
+                result.part,
+                json_answer(&result.answer),
+                result.elapsed.as_secs_f64()
+            ),
+            Format::Tsv => println!("{}", result.to_line()),
+        }
+    }
+}
+
+/// Answers are always written as JSON strings, even when they're integers. Some answers are bigger
+/// than 2^53, which JSON readers that use doubles would silently round.
+fn json_answer(answer: &str) -> String {
+    let mut json = String::from("\"");
+    for c in answer.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+
+    json
+}
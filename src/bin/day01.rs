@@ -1,41 +1,12 @@
-use anyhow::{Context, Result};
-use aoc_2020::input_lines;
+use anyhow::Result;
+use aoc_2020::solution::run_day;
 
 fn main() -> Result<()> {
-    let input = input_lines(1)?
-        .map(|line| {
-            line.context("Failed to read line")?
-                .parse::<i64>()
-                .context("Failed to parse number")
-        })
-        .collect::<Result<Vec<i64>>>()?;
+    env_logger::init();
 
-    part1(&input);
-    part2(&input);
-
-    Ok(())
-}
-
-fn part1(input: &[i64]) {
-    'outer: for x in input {
-        for y in input {
-            if x + y == 2020 {
-                println!("Found {} * {} = {}", x, y, x * y);
-                break 'outer;
-            }
-        }
+    for result in run_day(2020, 1, None)? {
+        println!("{}", result.description);
     }
-}
 
-fn part2(input: &[i64]) {
-    'outer: for x in input {
-        for y in input {
-            for z in input {
-                if x + y + z == 2020 {
-                    println!("Found {} * {} * {} = {}", x, y, z, x * y * z);
-                    break 'outer;
-                }
-            }
-        }
-    }
+    Ok(())
 }
@@ -1,65 +1,12 @@
-use anyhow::{Context, Result};
-use aoc_2020::input_lines;
+use anyhow::Result;
+use aoc_2020::solution::run_day;
 
 fn main() -> Result<()> {
-    let mut input = input_lines(6)?.peekable();
-    let mut chunks = Vec::new();
+    env_logger::init();
 
-    while input.peek().is_some() {
-        let chunk = input
-            .by_ref()
-            .take_while(|line| line.is_ok() && !line.as_ref().unwrap().is_empty())
-            .map(|line| line.context("Failed to read line"))
-            .collect::<Result<Vec<String>>>()?;
-        chunks.push(chunk);
+    for result in run_day(2020, 6, None)? {
+        println!("{}", result.description);
     }
 
-    part1(&chunks);
-    part2(&chunks);
-
     Ok(())
 }
-
-fn part1(chunks: &[Vec<String>]) {
-    fn generate_mask(input: &Vec<String>) -> u32 {
-        let mut value = 0;
-
-        for line in input {
-            for c in line.chars() {
-                value = value | (1 << c as u32 - 'a' as u32);
-            }
-        }
-
-        value
-    }
-
-    let count: u32 = chunks
-        .iter()
-        .map(|value| generate_mask(value).count_ones())
-        .sum();
-
-    println!("Total sum: {}", count);
-}
-
-fn part2(chunks: &[Vec<String>]) {
-    fn generate_mask(input: &Vec<String>) -> u32 {
-        let mut value = u32::MAX;
-
-        for line in input {
-            let mut inner = 0;
-            for c in line.chars() {
-                inner = inner | (1 << c as u32 - 'a' as u32);
-            }
-            value = value & inner;
-        }
-
-        value
-    }
-
-    let count: u32 = chunks
-        .iter()
-        .map(|value| generate_mask(value).count_ones())
-        .sum();
-
-    println!("Total sum: {}", count);
-}
@@ -0,0 +1,177 @@
+use lazy_static::lazy_static;
+use std::{
+    cell::Cell,
+    error::Error,
+    fmt::{self, Display},
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+use crate::bench::format_duration;
+
+lazy_static! {
+    static ref BUDGET: RwLock<Budget> = RwLock::new(Budget::default());
+}
+
+thread_local! {
+    static RUNNING: Cell<Option<Limits>> = const { Cell::new(None) };
+}
+
+/// How long a solver loop can keep going before giving up. The default has no limits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    /// The most iterations any one loop can make.
+    pub steps: Option<u64>,
+    /// How long a whole run can take, from parsing to the last part.
+    pub time: Option<Duration>,
+}
+
+impl Budget {
+    fn start(self) -> Limits {
+        Limits {
+            budget: self,
+            deadline: self.time.map(|time| Instant::now() + time),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Limits {
+    budget: Budget,
+    deadline: Option<Instant>,
+}
+
+/// A solver ran out of budget, most likely because its input is malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetExceeded {
+    Steps(u64),
+    Time(Duration),
+}
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetExceeded::Steps(steps) => {
+                write!(
+                    f,
+                    "Budget exceeded: a loop ran for more than {} steps",
+                    steps
+                )
+            }
+            BudgetExceeded::Time(time) => {
+                write!(
+                    f,
+                    "Budget exceeded: ran for longer than {}",
+                    format_duration(*time)
+                )
+            }
+        }
+    }
+}
+
+impl Error for BudgetExceeded {}
+
+/// Sets the budget for every subsequent run in this process.
+pub fn set_budget(budget: Budget) {
+    *BUDGET.write().unwrap() = budget;
+}
+
+pub fn budget() -> Budget {
+    *BUDGET.read().unwrap()
+}
+
+/// Runs `f` with `budget` applying on the current thread instead of the process-wide one. Its
+/// time limit counts from now.
+pub fn with_budget<R>(budget: Budget, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Limits>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            RUNNING.with(|limits| limits.set(self.0));
+        }
+    }
+
+    let _restore = Restore(RUNNING.with(|limits| limits.replace(Some(budget.start()))));
+
+    f()
+}
+
+/// Runs `f` under the process-wide budget, unless the current thread is already running under
+/// one.
+pub(crate) fn with_default_budget<R>(f: impl FnOnce() -> R) -> R {
+    match RUNNING.with(Cell::get) {
+        Some(_) => f(),
+        None => with_budget(budget(), f),
+    }
+}
+
+/// Starts counting the steps of a loop against the current budget.
+pub fn meter() -> Meter {
+    Meter {
+        steps: 0,
+        limits: RUNNING.with(Cell::get).unwrap_or_else(|| budget().start()),
+    }
+}
+
+/// Counts a solver loop's steps. Call `tick` once per iteration.
+#[derive(Debug)]
+pub struct Meter {
+    steps: u64,
+    limits: Limits,
+}
+
+impl Meter {
+    /// Counts a step, failing once the loop has gone over its budget.
+    pub fn tick(&mut self) -> Result<(), BudgetExceeded> {
+        self.steps += 1;
+
+        if let Some(steps) = self.limits.budget.steps {
+            if self.steps > steps {
+                return Err(BudgetExceeded::Steps(steps));
+            }
+        }
+
+        if let (Some(deadline), Some(time)) = (self.limits.deadline, self.limits.budget.time) {
+            if Instant::now() >= deadline {
+                return Err(BudgetExceeded::Time(time));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stops_after_step_limit() {
+        let budget = Budget {
+            steps: Some(3),
+            time: None,
+        };
+
+        with_budget(budget, || {
+            let mut meter = meter();
+            for _ in 0..3 {
+                assert_eq!(meter.tick(), Ok(()));
+            }
+            assert_eq!(meter.tick(), Err(BudgetExceeded::Steps(3)));
+        });
+
+        assert_eq!(meter().tick(), Ok(()));
+    }
+
+    #[test]
+    fn stops_after_deadline() {
+        let budget = Budget {
+            steps: None,
+            time: Some(Duration::ZERO),
+        };
+
+        with_budget(budget, || {
+            assert_eq!(meter().tick(), Err(BudgetExceeded::Time(Duration::ZERO)));
+        });
+    }
+}
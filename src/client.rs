@@ -0,0 +1,259 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{solution::Part, submit::Outcome};
+
+/// Environment variable holding the session cookie used to authenticate with the site.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// File read for the session cookie when `AOC_SESSION` isn't set.
+pub const DEFAULT_SESSION_FILE: &str = "./.aoc-session";
+
+/// Environment variable overriding the site's base URL, e.g. to point at a local stub server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/jamiebrynes7/aoc-2020";
+
+/// An authenticated connection to the Advent of Code site.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+/// What `fetch_input` did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    /// The input was already on disk so nothing was requested.
+    AlreadyPresent(PathBuf),
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Builds a client from `AOC_BASE_URL` and the session token. The token is read from
+    /// `session_file` if given, then `AOC_SESSION`, then `./.aoc-session`.
+    pub fn from_env(session_file: Option<&Path>) -> Result<Client> {
+        let session = match (session_file, env::var(SESSION_VAR)) {
+            (Some(path), _) => read_session(path)?,
+            (None, Ok(session)) => session,
+            (None, Err(_)) => read_session(Path::new(DEFAULT_SESSION_FILE)).with_context(|| {
+                format!(
+                    "No session token: set {} or write it to '{}'",
+                    SESSION_VAR, DEFAULT_SESSION_FILE
+                )
+            })?,
+        };
+
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+
+        Ok(Client::new(&base_url, &session))
+    }
+
+    /// Downloads the text of `year`'s `day` puzzle input.
+    pub fn input(&self, year: u16, day: u8) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+
+        match self.agent.get(&url).set("Cookie", &self.cookie()).call() {
+            Ok(response) => response
+                .into_string()
+                .with_context(|| format!("Failed to read the response from '{}'", url)),
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+
+                if body.contains("before it unlocks") {
+                    bail!("{} day {} isn't unlocked yet", year, day);
+                } else if body.contains("log in") {
+                    bail!("The session token was rejected");
+                } else if status == 404 {
+                    bail!("There is no puzzle for {} day {} ({})", year, day, url);
+                } else {
+                    bail!("'{}' returned {}: {}", url, status, body.trim());
+                }
+            }
+            Err(e) => Err(e).with_context(|| format!("Failed to request '{}'", url)),
+        }
+    }
+
+    /// Submits `answer` to `part` of `year`'s `day` and reports how the site responded.
+    pub fn submit(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<Outcome> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+
+        let page = match self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        {
+            Ok(response) => response
+                .into_string()
+                .with_context(|| format!("Failed to read the response from '{}'", url))?,
+            Err(ureq::Error::Status(status, response)) => bail!(
+                "'{}' returned {}: {}",
+                url,
+                status,
+                response.into_string().unwrap_or_default().trim()
+            ),
+            Err(e) => return Err(e).with_context(|| format!("Failed to post to '{}'", url)),
+        };
+
+        Outcome::parse(&page).with_context(|| format!("Unexpected page from '{}'", url))
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Downloads `year`'s `day` input to `path`, unless a non-empty file is already there.
+pub fn fetch_input(client: &Client, year: u16, day: u8, path: &Path) -> Result<Fetched> {
+    if has_input(path) {
+        return Ok(Fetched::AlreadyPresent(path.to_owned()));
+    }
+
+    let input = client.input(year, day)?;
+
+    // The first input of a new year needs its folder.
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create '{}'", dir.display()))?;
+    }
+
+    // Write to the side first so an interrupted download is never mistaken for a cached input.
+    let partial = path.with_extension("part");
+    fs::write(&partial, input)
+        .with_context(|| format!("Failed to write '{}'", partial.display()))?;
+    fs::rename(&partial, path)
+        .with_context(|| format!("Failed to move the input to '{}'", path.display()))?;
+
+    Ok(Fetched::Downloaded(path.to_owned()))
+}
+
+/// Whether `path` already holds an input. Empty files, like the ones `new` creates, don't count.
+pub fn has_input(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+fn read_session(path: &Path) -> Result<String> {
+    let session = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read session file '{}'", path.display()))?;
+
+    Ok(session.trim().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use tiny_http::{Response, Server};
+
+    /// Serves a single request with `status` and `body`, returning the base URL and a handle that
+    /// yields the requested URL and cookie.
+    fn stub(status: u16, body: &'static str) -> (String, thread::JoinHandle<(String, String)>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+
+        let handle = thread::spawn(move || {
+            let request = server.recv().unwrap();
+            let url = request.url().to_owned();
+            let cookie = request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv("Cookie"))
+                .map(|header| header.value.to_string())
+                .unwrap_or_default();
+
+            request
+                .respond(Response::from_string(body).with_status_code(status))
+                .unwrap();
+
+            (url, cookie)
+        });
+
+        (base_url, handle)
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("day01.txt")
+    }
+
+    #[test]
+    fn downloads_once() {
+        let (base_url, handle) = stub(200, "1721\n979\n");
+        let client = Client::new(&base_url, "token\n");
+        let path = scratch("downloads-once");
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            fetch_input(&client, 2020, 1, &path).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(
+            handle.join().unwrap(),
+            ("/2020/day/1/input".to_owned(), "session=token".to_owned())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1721\n979\n");
+
+        // The stub has shut down, so this would fail if it made a request.
+        assert_eq!(
+            fetch_input(&client, 2020, 1, &path).unwrap(),
+            Fetched::AlreadyPresent(path.clone())
+        );
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (base_url, handle) = stub(
+            200,
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        );
+
+        let outcome = Client::new(&base_url, "token")
+            .submit(2020, 1, Part::Two, "42")
+            .unwrap();
+        assert_eq!(
+            outcome,
+            Outcome::Incorrect {
+                hint: Some(crate::submit::Hint::TooLow),
+                wait: std::time::Duration::from_secs(60)
+            }
+        );
+        assert_eq!(handle.join().unwrap().0, "/2020/day/1/answer");
+    }
+
+    #[test]
+    fn not_unlocked() {
+        let (base_url, _) = stub(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        let path = scratch("not-unlocked");
+
+        let error = fetch_input(&Client::new(&base_url, "token"), 2020, 1, &path).unwrap_err();
+        assert_eq!(error.to_string(), "2020 day 1 isn't unlocked yet");
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn not_found() {
+        let (base_url, _) = stub(404, "404 Not Found");
+
+        let error = Client::new(&base_url, "token").input(2020, 1).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("There is no puzzle for 2020 day 1"));
+    }
+}
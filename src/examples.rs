@@ -0,0 +1,62 @@
+/// Declares a day's worked examples from the puzzle text. Each example becomes a unit test that
+/// parses `input` through the usual input functions and checks whichever answers are given.
+///
+/// ```ignore
+/// examples! {
+///     Day01;
+///
+///     expense_report {
+///         input: "1721\n979\n366\n299\n675\n1456",
+///         part1: 514579,
+///         part2: 241861950,
+///     }
+/// }
+/// ```
+macro_rules! examples {
+    (
+        $day:ident;
+        $(
+            $name:ident {
+                input: $input:expr
+                $(, part1: $part1:expr)?
+                $(, part2: $part2:expr)?
+                $(,)?
+            }
+        )*
+    ) => {
+        #[cfg(test)]
+        mod examples {
+            $(
+                #[test]
+                fn $name() -> anyhow::Result<()> {
+                    crate::examples::check::<super::$day>(
+                        $input,
+                        None $(.or(Some($part1.to_string())))?,
+                        None $(.or(Some($part2.to_string())))?,
+                    )
+                }
+            )*
+        }
+    };
+}
+
+#[cfg(test)]
+pub(crate) fn check<S: crate::Solution>(
+    input: &str,
+    part1: Option<String>,
+    part2: Option<String>,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let parsed = crate::with_input_text(input, S::parse).context("Failed to parse the example")?;
+
+    if let Some(expected) = part1 {
+        assert_eq!(S::part1(&parsed)?.to_string(), expected, "part 1");
+    }
+
+    if let Some(expected) = part2 {
+        assert_eq!(S::part2(&parsed)?.to_string(), expected, "part 2");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,218 @@
+//! Property tests that throw arbitrary and mutated lines at every day's line parser. A parser may
+//! reject anything it likes, but it must do so by returning an error rather than panicking.
+//!
+//! Failing inputs found by proptest are persisted under `proptest-regressions/` and replayed
+//! first on the next run. Crashers that have been fixed are also listed in `REGRESSIONS` so they
+//! keep being checked even if that directory is cleared.
+
+use proptest::prelude::*;
+use std::str::FromStr;
+
+use crate::y2020::{day02, day05, day06, day07, day08, day12, day13, day14, day16, day18, day19};
+
+/// Parses `line`, rendering the error so that diagnostics are exercised as well.
+fn check<T: FromStr>(line: &str) -> Result<(), String>
+where
+    T::Err: ToString,
+{
+    line.parse::<T>().map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// A parser under test, with well-formed lines for the mutations to start from.
+struct Target {
+    name: &'static str,
+    check: fn(&str) -> Result<(), String>,
+    samples: &'static [&'static str],
+}
+
+const TARGETS: &[Target] = &[
+    Target {
+        name: "day02::Policy",
+        check: check::<day02::Policy>,
+        samples: &["1-3 a: abcde", "2-9 c: ccccccccc"],
+    },
+    Target {
+        name: "day05::SeatId",
+        check: check::<day05::SeatId>,
+        samples: &["FBFBBFFRLR", "BBFFBBFRLL"],
+    },
+    Target {
+        name: "day06::Answers",
+        check: check::<day06::Answers>,
+        samples: &["abc", "xyz"],
+    },
+    Target {
+        name: "day07::Relationship",
+        check: check::<day07::Relationship>,
+        samples: &[
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.",
+            "faded blue bags contain no other bags.",
+        ],
+    },
+    Target {
+        name: "day08::OpCode",
+        check: check::<day08::OpCode>,
+        samples: &["nop +0", "acc -99", "jmp +4"],
+    },
+    Target {
+        name: "day12::Action",
+        check: check::<day12::Action>,
+        samples: &["F10", "N3", "R90"],
+    },
+    Target {
+        name: "day13::BusTimetable",
+        check: check::<day13::BusTimetable>,
+        samples: &["7,13,x,x,59,x,31,19"],
+    },
+    Target {
+        name: "day14::Instruction",
+        check: check::<day14::Instruction>,
+        samples: &["mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X", "mem[8] = 11"],
+    },
+    Target {
+        name: "day16::Constraint",
+        check: check::<day16::Constraint>,
+        samples: &["class: 1-3 or 5-7", "departure location: 49-258 or 268-960"],
+    },
+    Target {
+        name: "day16::Ticket",
+        check: check::<day16::Ticket>,
+        samples: &["7,1,14"],
+    },
+    Target {
+        name: "day18::Expression",
+        check: check::<day18::Expression>,
+        samples: &["1 + (2 * 3) + (4 * (5 + 6))", "2 * 3 + (4 * 5)"],
+    },
+    Target {
+        name: "day19::Expression",
+        check: check::<day19::Expression>,
+        samples: &["4 1 5", "2 3 | 3 2", "\"a\""],
+    },
+];
+
+/// Inputs that used to panic, keyed by the target that now rejects them. Days 6 and 18 had no
+/// parser for these lines, so they crashed while solving instead.
+const REGRESSIONS: &[(&str, &str)] = &[
+    ("day05::SeatId", "FBFBBFFRLX"),
+    ("day06::Answers", "A"),
+    ("day06::Answers", "é"),
+    ("day07::Relationship", "light red bags"),
+    ("day08::OpCode", "nop"),
+    ("day08::OpCode", "nip +0"),
+    ("day12::Action", ""),
+    ("day12::Action", "X10"),
+    ("day12::Action", "éé"),
+    ("day13::BusTimetable", "7,y"),
+    ("day13::BusTimetable", "0,x,7"),
+    ("day14::Instruction", "mask"),
+    ("day14::Instruction", "mem[8]"),
+    ("day16::Constraint", "class"),
+    ("day16::Constraint", "class: 1 or 5-7"),
+    ("day16::Ticket", "7,,14"),
+    ("day18::Expression", "1 2"),
+    ("day18::Expression", "+ 1"),
+    ("day19::Expression", "1 |"),
+];
+
+/// Small edits to a line: deleting, inserting, replacing or truncating at a position.
+#[derive(Debug, Clone)]
+enum Mutation {
+    Delete(usize),
+    Insert(usize, char),
+    Replace(usize, char),
+    Truncate(usize),
+}
+
+impl Mutation {
+    fn apply(&self, line: &mut Vec<char>) {
+        let at = |position: usize, len: usize| position % (len + 1);
+
+        match *self {
+            Mutation::Delete(position) if !line.is_empty() => {
+                line.remove(position % line.len());
+            }
+            Mutation::Insert(position, c) => line.insert(at(position, line.len()), c),
+            Mutation::Replace(position, c) if !line.is_empty() => {
+                let index = position % line.len();
+                line[index] = c;
+            }
+            Mutation::Truncate(position) => line.truncate(at(position, line.len())),
+            _ => {}
+        }
+    }
+}
+
+/// Characters that appear in the puzzle inputs, so that mutations stay close to real lines, with
+/// an occasional arbitrary character.
+fn character() -> impl Strategy<Value = char> {
+    prop_oneof![
+        9 => proptest::sample::select(" ,:-+*|()[]=\"0123456789abcxyzFBLRNSEWX".chars().collect::<Vec<_>>()),
+        1 => any::<char>(),
+    ]
+}
+
+fn mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        any::<usize>().prop_map(Mutation::Delete),
+        (any::<usize>(), character()).prop_map(|(p, c)| Mutation::Insert(p, c)),
+        (any::<usize>(), character()).prop_map(|(p, c)| Mutation::Replace(p, c)),
+        any::<usize>().prop_map(Mutation::Truncate),
+    ]
+}
+
+fn mutated(sample: &str, mutations: &[Mutation]) -> String {
+    let mut line = sample.chars().collect::<Vec<_>>();
+    for mutation in mutations {
+        mutation.apply(&mut line);
+    }
+
+    line.into_iter().collect()
+}
+
+#[test]
+fn regressions() {
+    for (name, line) in REGRESSIONS {
+        let target = TARGETS
+            .iter()
+            .find(|target| target.name == *name)
+            .unwrap_or_else(|| panic!("No fuzz target named {}", name));
+
+        assert!(
+            (target.check)(line).is_err(),
+            "{} accepted {:?}, which it should reject",
+            name,
+            line
+        );
+    }
+}
+
+#[test]
+fn samples_parse() {
+    for target in TARGETS {
+        for sample in target.samples {
+            if let Err(e) = (target.check)(sample) {
+                panic!("{} rejected its own sample:\n{}", target.name, e);
+            }
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_lines(target in 0..TARGETS.len(), line in "\\PC{0,64}") {
+        let _ = (TARGETS[target].check)(&line);
+    }
+
+    #[test]
+    fn mutated_lines(
+        target in 0..TARGETS.len(),
+        sample in any::<usize>(),
+        mutations in prop::collection::vec(mutation(), 1..6),
+    ) {
+        let target = &TARGETS[target];
+        let sample = target.samples[sample % target.samples.len()];
+
+        let _ = (target.check)(&mutated(sample, &mutations));
+    }
+}
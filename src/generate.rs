@@ -0,0 +1,112 @@
+use anyhow::Result;
+use std::ops::Range;
+
+use crate::solution;
+
+/// Writes a synthetic input for `year`'s `day`. The same `size` and `seed` always give the same
+/// input.
+///
+/// What `size` counts depends on the day, but it is roughly the number of lines or records.
+pub fn generate(year: u16, day: u8, size: usize, seed: u64) -> Result<String> {
+    (solution::find(year, day)?.generate)(&mut Rng::new(seed), size)
+}
+
+/// Joins `lines` into the text of an input file, ending each with a newline.
+pub fn lines<I>(lines: I) -> String
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    lines.into_iter().fold(String::new(), |mut text, line| {
+        text.push_str(line.as_ref());
+        text.push('\n');
+        text
+    })
+}
+
+/// A small seeded random number generator (SplitMix64). It's written out here rather than taken
+/// from a crate so that a seed keeps producing the same input across dependency upgrades.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: Range<usize>) -> usize {
+        assert!(!range.is_empty(), "Empty range {:?}", range);
+
+        let width = (range.end - range.start) as u128;
+        range.start + ((self.next_u64() as u128 * width) >> 64) as usize
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// A random lowercase ASCII letter.
+    pub fn letter(&mut self) -> char {
+        (b'a' + self.range(0..26) as u8) as char
+    }
+
+    /// A random element of `items`, which must not be empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solution::SOLUTIONS, with_input_text};
+
+    /// Every generated input should parse and solve. Day 15's part 2 is skipped as it takes
+    /// seconds whatever the input.
+    #[test]
+    fn inputs_solve() {
+        for reg in SOLUTIONS {
+            for seed in 0..3 {
+                let input = generate(reg.year, reg.day, 8, seed).unwrap();
+                let part = if reg.day == 15 {
+                    Some(solution::Part::One)
+                } else {
+                    None
+                };
+
+                with_input_text(&input, || (reg.run)(part)).unwrap_or_else(|e| {
+                    panic!("Day {} seed {} failed: {:?}\n{}", reg.day, seed, e, input)
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        for reg in SOLUTIONS {
+            assert_eq!(
+                generate(reg.year, reg.day, 20, 7).unwrap(),
+                generate(reg.year, reg.day, 20, 7).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use anyhow::{bail, Result};
+use std::{
+    fmt::{self, Display},
+    ops::Index,
+};
+
+use crate::ParseError;
+
+/// Offsets to the four orthogonal neighbours of a cell, as `(x, y)`.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to all eight neighbours of a cell, including diagonals, as `(x, y)`.
+pub const ADJACENT: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular 2D map of cells, addressed as `(x, y)` with `(0, 0)` in the top-left corner.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from lines of text, converting each character with `cell`. Returning `None`
+    /// from `cell` marks the character as invalid. Errors point at the line and column, counting
+    /// the first line as line 1.
+    pub fn parse<I, S, F>(lines: I, mut cell: F) -> Result<Grid<T>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (y, line) in lines.into_iter().enumerate() {
+            let line = line.as_ref();
+            let row_start = cells.len();
+
+            for (index, c) in line.char_indices() {
+                cells.push(cell(c).ok_or_else(|| {
+                    ParseError::new(
+                        line,
+                        index..index + c.len_utf8(),
+                        format!("Unexpected character '{}'", c),
+                    )
+                    .with_line(y + 1)
+                })?);
+            }
+
+            let row_width = cells.len() - row_start;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(ParseError::whole(
+                        line,
+                        format!("Row is {} cells wide, expected {}", row_width, width),
+                    )
+                    .with_line(y + 1)
+                    .into())
+                }
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => bail!("Grid is empty"),
+        }
+    }
+
+    /// Builds a grid by calling `f` with the position of every cell. Like `parse`, an empty grid
+    /// is an error.
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Result<Grid<T>>
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        if width == 0 || height == 0 {
+            bail!("Grid is empty");
+        }
+
+        let mut cells = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+
+        Some(&self.cells[y as usize * self.width + x as usize])
+    }
+
+    /// Like `get`, but coordinates outside the grid wrap around to the opposite edge.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+
+        &self.cells[y * self.width + x]
+    }
+
+    /// Iterates over every cell in row-major order along with its position.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index % self.width, index / self.width), cell))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width)
+    }
+
+    /// The in-bounds orthogonal neighbours of `(x, y)`.
+    pub fn neighbours4(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.offsets(x, y, &ORTHOGONAL)
+    }
+
+    /// The in-bounds neighbours of `(x, y)`, including diagonals.
+    pub fn neighbours8(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.offsets(x, y, &ADJACENT)
+    }
+
+    /// Walks from `(x, y)` in steps of `direction` until leaving the grid. The starting cell is
+    /// not included.
+    pub fn ray(
+        &self,
+        x: usize,
+        y: usize,
+        direction: (isize, isize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        (1..).map_while(move |step| {
+            let x = x as isize + direction.0 * step;
+            let y = y as isize + direction.1 * step;
+            self.get(x, y).map(|cell| ((x as usize, y as usize), cell))
+        })
+    }
+
+    /// Builds a new grid of the same size by calling `f` on every cell.
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut((usize, usize), &T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(pos, cell)| f(pos, cell)).collect(),
+        }
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let x = x as isize + dx;
+            let y = y as isize + dy;
+            self.get(x, y).map(|cell| ((x as usize, y as usize), cell))
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside of a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse(["123", "456"], |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parses_rows() {
+        let grid = digits();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    fn rejects_bad_shapes() {
+        let error = Grid::parse(["12", "3"], |c| c.to_digit(10)).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Row is 1 cells wide, expected 2 at line 2"));
+
+        let error = Grid::parse(["1x"], |c| c.to_digit(10)).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Unexpected character 'x' at line 1, column 2"));
+
+        assert!(Grid::parse(Vec::<&str>::new(), |c| c.to_digit(10)).is_err());
+        assert!(Grid::parse([""], |c| c.to_digit(10)).is_err());
+        assert!(Grid::from_fn(0, 3, |_, _| 0).is_err());
+        assert!(Grid::from_fn(3, 0, |_, _| 0).is_err());
+    }
+
+    #[test]
+    fn indexes_edges() {
+        let grid = Grid::from_fn(3, 2, |x, y| x + 10 * y).unwrap();
+
+        assert_eq!(grid.get(0, 0), Some(&0));
+        assert_eq!(grid.get(2, 1), Some(&12));
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+
+        assert_eq!(*grid.get_wrapping(-1, 0), 2);
+        assert_eq!(*grid.get_wrapping(3, 2), 0);
+        assert_eq!(*grid.get_wrapping(7, -1), 11);
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = digits();
+
+        let corner = grid.neighbours4(0, 0).collect::<Vec<_>>();
+        assert_eq!(corner, [((1, 0), &2), ((0, 1), &4)]);
+
+        let middle = grid.neighbours8(1, 0).map(|(_, n)| *n).collect::<Vec<_>>();
+        assert_eq!(middle, [1, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn casts_rays() {
+        let grid = Grid::from_fn(4, 4, |x, y| (x, y)).unwrap();
+
+        let right = grid
+            .ray(1, 1, (1, 0))
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+        assert_eq!(right, [(2, 1), (3, 1)]);
+
+        let diagonal = grid
+            .ray(0, 0, (1, 1))
+            .map(|(_, cell)| *cell)
+            .collect::<Vec<_>>();
+        assert_eq!(diagonal, [(1, 1), (2, 2), (3, 3)]);
+
+        assert_eq!(grid.ray(0, 0, (-1, 0)).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside of a 3x2 grid")]
+    fn index_out_of_bounds() {
+        let _ = digits()[(3, 0)];
+    }
+}
@@ -1,13 +1,213 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::{
-    fs::File,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead},
+    ops::Deref,
+    str::FromStr,
+    vec,
+};
+
+pub mod answers;
+pub mod bench;
+pub mod budget;
+pub mod client;
+#[macro_use]
+mod examples;
+#[cfg(test)]
+mod fuzz;
+pub mod generate;
+pub mod grid;
+pub mod memory;
+pub mod parse_error;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod source;
+pub mod submit;
+pub mod watch;
+pub mod y2020;
+
+pub use grid::Grid;
+pub use parse_error::{parse_field, ParseError};
+pub use solution::Solution;
+pub use source::{
+    input_path, set_input_source, with_input_file, with_input_text, InputSource, InputText,
+    Location,
 };
 
 pub fn input_lines(
+    year: u16,
     day: u8,
 ) -> Result<impl Iterator<Item = std::result::Result<String, io::Error>>> {
-    let file = File::open(format!("./src/input/day{:0width$}.txt", day, width = 2))?;
+    Ok(source::locate(year, day).open()?.lines())
+}
+
+/// The whole input, memory-mapped when it's a file. Its `lines()` borrow from the mapping, which
+/// avoids allocating a `String` per line on huge inputs.
+///
+/// # Safety
+///
+/// Nothing may truncate or rewrite the input file while the result is alive; see
+/// `Location::map`. Prefer `input_lines` unless the input is too big to read line by line.
+pub unsafe fn input_mapped(year: u16, day: u8) -> Result<InputText> {
+    source::locate(year, day).map()
+}
+
+/// Parses every line of the input as a `T`. Failures are reported as a `ParseError` for the
+/// offending line, in the context of the input location.
+pub fn input_parsed<T>(year: u16, day: u8) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    let location = source::locate(year, day);
+    let lines = location.open()?.lines().enumerate().map(|(index, line)| {
+        line.with_context(|| format!("Failed to read line {} of {}", index + 1, location))
+    });
+
+    parse_lines(&location, lines)
+}
+
+/// Like `input_parsed`, but the lines are borrowed from `input_mapped`, so only the parsed values
+/// are allocated.
+///
+/// # Safety
+///
+/// The same as `input_mapped`.
+pub unsafe fn input_mapped_parsed<T>(year: u16, day: u8) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+{
+    let location = source::locate(year, day);
+    let text = location.map()?;
+    let lines = text
+        .lines()
+        .map(|line| line.with_context(|| format!("Failed to read {}", location)));
+
+    parse_lines(&location, lines)
+}
+
+fn parse_lines<T, S>(location: &Location, lines: impl Iterator<Item = Result<S>>) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Into<anyhow::Error>,
+    S: AsRef<str>,
+{
+    lines
+        .enumerate()
+        .map(|(index, line)| {
+            let line = line?;
+            let line = line.as_ref();
+
+            line.parse::<T>().map_err(|error| {
+                let error = match error.into().downcast::<ParseError>() {
+                    Ok(error) => error,
+                    Err(error) => ParseError::whole(line, format!("Invalid value: {:#}", error)),
+                };
+
+                anyhow::Error::new(error.with_line(index + 1))
+                    .context(format!("Failed to parse {}", location))
+            })
+        })
+        .collect()
+}
+
+/// Parses the input as a character map, converting each character with `cell`.
+pub fn input_grid<T, F>(year: u16, day: u8, cell: F) -> Result<Grid<T>>
+where
+    F: FnMut(char) -> Option<T>,
+{
+    let lines = input_lines(year, day)?.collect::<std::result::Result<Vec<String>, io::Error>>()?;
+
+    Grid::parse(lines, cell)
+}
+
+/// Groups the input lines into records separated by one or more blank lines.
+pub fn input_records(
+    year: u16,
+    day: u8,
+) -> Result<impl Iterator<Item = std::result::Result<Record, io::Error>>> {
+    Ok(Records {
+        lines: input_lines(year, day)?,
+        line_number: 0,
+    })
+}
+
+/// A group of consecutive non-blank lines from `input_records`. Derefs to the lines themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    first_line: usize,
+    lines: Vec<String>,
+}
+
+impl Record {
+    /// The 1-based line number within the input of the record's line at `index`.
+    pub fn line_number(&self, index: usize) -> usize {
+        self.first_line + index
+    }
+
+    pub fn into_lines(self) -> Vec<String> {
+        self.lines
+    }
+}
+
+impl Deref for Record {
+    type Target = [String];
+
+    fn deref(&self) -> &Self::Target {
+        &self.lines
+    }
+}
+
+impl IntoIterator for Record {
+    type Item = String;
+    type IntoIter = vec::IntoIter<String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.lines.into_iter()
+    }
+}
+
+struct Records<I> {
+    lines: I,
+    line_number: usize,
+}
+
+impl<I> Iterator for Records<I>
+where
+    I: Iterator<Item = std::result::Result<String, io::Error>>,
+{
+    type Item = std::result::Result<Record, io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = Record {
+            first_line: 0,
+            lines: Vec::new(),
+        };
+
+        for line in &mut self.lines {
+            self.line_number += 1;
+
+            match line {
+                Ok(line) if line.is_empty() => {
+                    if !record.is_empty() {
+                        return Some(Ok(record));
+                    }
+                }
+                Ok(line) => {
+                    if record.is_empty() {
+                        record.first_line = self.line_number;
+                    }
+                    record.lines.push(line);
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
 
-    Ok(BufReader::new(file).lines())
+        if record.is_empty() {
+            None
+        } else {
+            Some(Ok(record))
+        }
+    }
 }
@@ -0,0 +1,218 @@
+use anyhow::{bail, Result};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering},
+};
+
+use crate::{
+    bench::Phase,
+    solution::{self, Part, PartResult, Solution},
+};
+
+/// A global allocator that passes everything on to the system allocator, counting what it
+/// allocates once `enable` has been called. A binary opts in with:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+// Signed, as memory allocated before counting started may be freed after.
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+fn record_alloc(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+
+        let live = LIVE.fetch_add(size as isize, Ordering::Relaxed) + size as isize;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+}
+
+fn record_dealloc(size: usize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        LIVE.fetch_sub(size as isize, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    /// Counted as freeing the old block and allocating the new one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+/// Starts counting allocations. Fails if `CountingAllocator` isn't the global allocator, as
+/// nothing would be counted.
+pub fn enable() -> Result<()> {
+    ENABLED.store(true, Ordering::SeqCst);
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    drop(black_box(Box::new(0u8)));
+
+    if ALLOCATIONS.load(Ordering::SeqCst) == before {
+        ENABLED.store(false, Ordering::SeqCst);
+        bail!("Allocations can't be counted as the counting allocator isn't installed");
+    }
+
+    Ok(())
+}
+
+/// What was allocated while running something.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Allocations {
+    /// Calls to allocate or reallocate memory.
+    pub count: u64,
+    /// Bytes requested by those calls.
+    pub bytes: u64,
+    /// The most bytes that were live at once, on top of what already was when it started.
+    pub peak: u64,
+}
+
+/// Runs `f`, counting what it allocates. Everything is zero unless `enable` has been called.
+///
+/// The counts are global, so allocations made by other threads in the meantime are included.
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Allocations) {
+    let count = ALLOCATIONS.load(Ordering::SeqCst);
+    let bytes = BYTES.load(Ordering::SeqCst);
+    let live = LIVE.load(Ordering::SeqCst);
+    PEAK.store(live, Ordering::SeqCst);
+
+    let value = f();
+
+    let allocations = Allocations {
+        count: ALLOCATIONS.load(Ordering::SeqCst) - count,
+        bytes: BYTES.load(Ordering::SeqCst) - bytes,
+        peak: (PEAK.load(Ordering::SeqCst) - live).max(0) as u64,
+    };
+
+    (value, allocations)
+}
+
+/// What one phase of a day allocated.
+#[derive(Debug, Copy, Clone)]
+pub struct PhaseAllocations {
+    pub year: u16,
+    pub day: u8,
+    pub phase: Phase,
+    pub allocations: Allocations,
+}
+
+/// The answers from `profile`, along with what each phase allocated.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub results: Vec<PartResult>,
+    pub phases: Vec<PhaseAllocations>,
+}
+
+/// Runs `S` like `solution::run`, also measuring what parsing and each part allocate.
+pub fn profile<S: Solution>(part: Option<Part>) -> Result<Profile> {
+    let mut phases = Vec::new();
+
+    let results = solution::run_with::<S>(part, |phase, f| {
+        let ((), allocations) = measure(f);
+        phases.push(PhaseAllocations {
+            year: S::YEAR,
+            day: S::DAY,
+            phase,
+            allocations,
+        });
+    })?;
+
+    Ok(Profile { results, phases })
+}
+
+/// Formats a byte count with a binary unit and about three significant figures.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    let decimals = if value >= 100.0 {
+        0
+    } else if value >= 10.0 {
+        1
+    } else {
+        2
+    };
+
+    format!("{:.*}{}", decimals, value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    // Other tests run alongside this one and allocate too, so only lower bounds can be checked.
+    #[test]
+    fn counts_allocations() {
+        enable().unwrap();
+
+        let (_, allocations) = measure(|| {
+            let small = black_box(vec![0u8; 1000]);
+            let large = black_box(vec![0u8; 4000]);
+            (small, large)
+        });
+
+        assert!(allocations.count >= 2);
+        assert!(allocations.bytes >= 5000);
+        assert!(allocations.peak >= 5000);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0B");
+        assert_eq!(format_bytes(1023), "1023B");
+        assert_eq!(format_bytes(1536), "1.50KiB");
+        assert_eq!(format_bytes(20 * 1024 * 1024), "20.0MiB");
+        assert_eq!(format_bytes(300 * 1024 * 1024 * 1024), "300GiB");
+    }
+}
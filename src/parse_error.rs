@@ -0,0 +1,171 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    ops::Range,
+    str::FromStr,
+};
+
+/// A problem with a line of puzzle input, pointing at the part of the line that caused it.
+///
+/// Displays as the message, followed by the line with the offending span underlined:
+///
+/// ```text
+/// Unknown op code 'nip' at line 3, column 1
+///   |
+/// 3 | nip +0
+///   | ^^^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    text: String,
+    span: Range<usize>,
+    line: Option<usize>,
+}
+
+impl ParseError {
+    /// An error about the bytes in `span` of `text`. The span is clamped to the text.
+    pub fn new(text: &str, span: Range<usize>, message: impl Into<String>) -> ParseError {
+        let end = span.end.min(text.len());
+        let start = span.start.min(end);
+
+        ParseError {
+            message: message.into(),
+            text: text.to_owned(),
+            span: start..end,
+            line: None,
+        }
+    }
+
+    /// An error about `part`, which should be a slice of `text` such as one returned by `split`.
+    /// Anything else is treated as an error about the whole of `text`.
+    pub fn at(text: &str, part: &str, message: impl Into<String>) -> ParseError {
+        let start = (part.as_ptr() as usize).wrapping_sub(text.as_ptr() as usize);
+
+        if start <= text.len() && start + part.len() <= text.len() {
+            ParseError::new(text, start..start + part.len(), message)
+        } else {
+            ParseError::whole(text, message)
+        }
+    }
+
+    /// An error about the whole of `text`.
+    pub fn whole(text: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(text, 0..text.len(), message)
+    }
+
+    /// An error about something missing from the end of `text`.
+    pub fn end(text: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(text, text.len()..text.len(), message)
+    }
+
+    /// Sets the 1-based line number of the text within the input.
+    pub fn with_line(mut self, line: usize) -> ParseError {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The line of input the error is about.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The 1-based line number, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// The 1-based column, in characters, where the span starts.
+    pub fn column(&self) -> usize {
+        self.text[..self.span.start].chars().count() + 1
+    }
+
+    /// The byte range of `text` that the error is about.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = self.line.map(|line| line.to_string()).unwrap_or_default();
+        let blank = " ".repeat(gutter.len());
+
+        match self.line {
+            Some(line) => write!(
+                f,
+                "{} at line {}, column {}",
+                self.message,
+                line,
+                self.column()
+            )?,
+            None => write!(f, "{} at column {}", self.message, self.column())?,
+        }
+
+        let width = self.text[self.span.clone()].chars().count().max(1);
+
+        write!(
+            f,
+            "\n{blank} |\n{gutter} | {text}\n{blank} | {indent}{carets}",
+            blank = blank,
+            gutter = gutter,
+            text = self.text,
+            indent = " ".repeat(self.column() - 1),
+            carets = "^".repeat(width)
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `part` of `text` as a `T`, pointing at `part` if it isn't one. `what` names the value
+/// in the message, e.g. "Invalid number 'x'".
+pub fn parse_field<T>(text: &str, part: &str, what: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+{
+    part.parse::<T>()
+        .map_err(|_| ParseError::at(text, part, format!("Invalid {} '{}'", what, part)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn underlines_the_span() {
+        let line = "nip +0";
+        let error = ParseError::at(line, &line[..3], "Unknown op code 'nip'").with_line(3);
+
+        assert_eq!(error.column(), 1);
+        assert_eq!(
+            error.to_string(),
+            "Unknown op code 'nip' at line 3, column 1\n  |\n3 | nip +0\n  | ^^^"
+        );
+    }
+
+    #[test]
+    fn points_past_the_end() {
+        let error = ParseError::end("FBF", "Expected 10 characters").with_line(12);
+
+        assert_eq!(error.column(), 4);
+        assert_eq!(
+            error.to_string(),
+            "Expected 10 characters at line 12, column 4\n   |\n12 | FBF\n   |    ^"
+        );
+    }
+
+    #[test]
+    fn points_at_fields() {
+        let line = "1-x a: abc";
+        let error = parse_field::<usize>(line, &line[2..3], "maximum").unwrap_err();
+
+        assert_eq!(error.message(), "Invalid maximum 'x'");
+        assert_eq!(error.span(), 2..3);
+        assert_eq!(error.line(), None);
+    }
+}
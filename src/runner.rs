@@ -0,0 +1,288 @@
+use std::{
+    any::Any,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    budget::{self, with_budget, Budget},
+    solution::{Part, PartResult, Registration},
+    with_input_file,
+};
+
+/// How a day finished when run in isolation.
+#[derive(Debug)]
+pub enum Status {
+    Ok(Vec<PartResult>),
+    Error(anyhow::Error),
+    /// The day panicked, with the panic message.
+    Panic(String),
+    /// The day was still running when its time ran out.
+    Timeout,
+}
+
+/// The result of running a single day, including parsing.
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: u8,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+impl DayReport {
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status, Status::Ok(_))
+    }
+}
+
+/// Runs one day, turning errors and panics into a `Status` instead of passing them on.
+pub fn run_isolated(reg: &Registration, part: Option<Part>) -> DayReport {
+    let start = Instant::now();
+
+    let status = match panic::catch_unwind(AssertUnwindSafe(|| (reg.run)(part))) {
+        Ok(Ok(results)) => Status::Ok(results),
+        Ok(Err(e)) => Status::Error(e),
+        Err(payload) => Status::Panic(panic_message(payload)),
+    };
+
+    DayReport {
+        day: reg.day,
+        status,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Runs every day in `days` on a pool of `jobs` threads. Reports come back in the same order as
+/// `days`, and one day failing doesn't stop the others.
+pub fn run_parallel(days: &[&Registration], part: Option<Part>, jobs: usize) -> Vec<DayReport> {
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(Vec::with_capacity(days.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let reg = match days.get(index) {
+                    Some(reg) => reg,
+                    None => break,
+                };
+
+                let report = run_isolated(reg, part);
+                reports.lock().unwrap().push((index, report));
+            });
+        }
+    });
+
+    let mut reports = reports.into_inner().unwrap();
+    reports.sort_by_key(|(index, _)| *index);
+
+    reports.into_iter().map(|(_, report)| report).collect()
+}
+
+/// The result of running a day on one of several inputs.
+#[derive(Debug)]
+pub struct InputReport {
+    pub path: PathBuf,
+    pub report: DayReport,
+}
+
+/// Every file directly inside `dir`, sorted by name.
+pub fn input_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read '{}'", dir.display()))? {
+        let path = entry
+            .with_context(|| format!("Failed to read '{}'", dir.display()))?
+            .path();
+
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+    Ok(paths)
+}
+
+/// Runs `reg` on each of `paths` in turn, giving each run `timeout` to finish.
+///
+/// Each run happens on a thread of its own, with `timeout` as its time budget so that solver
+/// loops give up once it's spent. A run stuck anywhere else is reported as `Status::Timeout`,
+/// but its thread can't be stopped and carries on in the background, which may slow down the
+/// runs after it.
+pub fn run_inputs(
+    reg: &'static Registration,
+    paths: &[PathBuf],
+    part: Option<Part>,
+    timeout: Duration,
+) -> Vec<InputReport> {
+    paths
+        .iter()
+        .map(|path| {
+            let (sender, receiver) = mpsc::channel();
+            let start = Instant::now();
+
+            let thread_path = path.clone();
+            thread::spawn(move || {
+                let budget = Budget {
+                    time: Some(timeout),
+                    ..budget::budget()
+                };
+                let report = with_budget(budget, || {
+                    with_input_file(&thread_path, || run_isolated(reg, part))
+                });
+                // Nobody is listening any more if the run timed out.
+                let _ = sender.send(report);
+            });
+
+            let report = receiver
+                .recv_timeout(timeout)
+                .unwrap_or_else(|_| DayReport {
+                    day: reg.day,
+                    status: Status::Timeout,
+                    elapsed: start.elapsed(),
+                });
+
+            InputReport {
+                path: path.clone(),
+                report,
+            }
+        })
+        .collect()
+}
+
+/// The number of threads to use when none is given.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => (*message).to_owned(),
+            Err(_) => "<unknown panic>".to_owned(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solution, with_input_text};
+    use anyhow::bail;
+    use std::env;
+
+    /// Day 1's registration, renumbered as `day` and running `run` instead.
+    fn stub(day: u8, run: fn(Option<Part>) -> Result<Vec<PartResult>>) -> Registration {
+        Registration {
+            day,
+            run,
+            ..*solution::find(2020, 1).unwrap()
+        }
+    }
+
+    fn fails(_: Option<Part>) -> Result<Vec<PartResult>> {
+        bail!("no answer")
+    }
+
+    fn panics(_: Option<Part>) -> Result<Vec<PartResult>> {
+        panic!("boom")
+    }
+
+    fn slow(part: Option<Part>) -> Result<Vec<PartResult>> {
+        thread::sleep(Duration::from_millis(50));
+        with_input_text("1721\n299\n", || solution::run_day(2020, 1, part))
+    }
+
+    fn hangs(_: Option<Part>) -> Result<Vec<PartResult>> {
+        thread::sleep(Duration::from_secs(2));
+        bail!("woke up")
+    }
+
+    fn answers(report: &DayReport) -> Vec<&str> {
+        match &report.status {
+            Status::Ok(results) => results.iter().map(|r| r.answer.as_str()).collect(),
+            status => panic!("Day {} didn't finish: {:?}", report.day, status),
+        }
+    }
+
+    #[test]
+    fn isolates_errors_and_panics() {
+        let report = run_isolated(&stub(1, slow), Some(Part::One));
+        assert_eq!(answers(&report), ["514579"]);
+
+        let report = run_isolated(&stub(2, fails), None);
+        assert!(matches!(&report.status, Status::Error(e) if e.to_string() == "no answer"));
+
+        let report = run_isolated(&stub(3, panics), None);
+        assert!(matches!(&report.status, Status::Panic(message) if message == "boom"));
+        assert_eq!(report.day, 3);
+    }
+
+    #[test]
+    fn parallel_keeps_order() {
+        let days = [
+            stub(1, slow),
+            stub(2, panics),
+            stub(3, fails),
+            stub(4, slow),
+        ];
+        let days = days.iter().collect::<Vec<_>>();
+
+        let reports = run_parallel(&days, Some(Part::One), 4);
+
+        assert_eq!(
+            reports.iter().map(|report| report.day).collect::<Vec<_>>(),
+            [1, 2, 3, 4]
+        );
+        assert_eq!(answers(&reports[0]), ["514579"]);
+        assert!(matches!(reports[1].status, Status::Panic(_)));
+        assert!(matches!(reports[2].status, Status::Error(_)));
+        assert_eq!(answers(&reports[3]), ["514579"]);
+    }
+
+    #[test]
+    fn runs_each_input() {
+        let dir = env::temp_dir().join(format!("aoc-runner-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("b.txt"), "1721\n979\n366\n299\n675\n1456\n").unwrap();
+        fs::write(dir.join("a.txt"), "1721\nx\n").unwrap();
+
+        let paths = input_files(&dir).unwrap();
+        assert_eq!(paths, [dir.join("a.txt"), dir.join("b.txt")]);
+
+        let reg = solution::find(2020, 1).unwrap();
+        let reports = run_inputs(reg, &paths, None, Duration::from_secs(10));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(reports[0].path, paths[0]);
+        assert!(matches!(reports[0].report.status, Status::Error(_)));
+        assert_eq!(answers(&reports[1].report), ["514579", "241861950"]);
+    }
+
+    #[test]
+    fn times_out_hung_inputs() {
+        let reg: &'static Registration = Box::leak(Box::new(stub(1, hangs)));
+        let start = Instant::now();
+
+        let reports = run_inputs(
+            reg,
+            &[PathBuf::from("unused.txt")],
+            None,
+            Duration::from_millis(50),
+        );
+
+        assert!(matches!(reports[0].report.status, Status::Timeout));
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,363 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::solution::DEFAULT_YEAR;
+
+/// Creates the module, binary, empty input and example test stub for `year`'s `day` under the
+/// crate root `root`, and registers the day in the year's `mod.rs` and in `solution.rs`. The
+/// year's module is created and declared in `lib.rs` if this is its first day.
+///
+/// Nothing is written if any of the files already exist or the day is already registered.
+/// Returns every file that was created or changed.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        bail!("Day must be between 1 and 25, got {}", day);
+    }
+
+    let name = format!("day{:02}", day);
+    let year_dir = root.join(format!("src/y{}", year));
+    let input_dir = root.join(format!("src/input/{}", year));
+    let module_path = year_dir.join(format!("{}.rs", name));
+    let bin_path = root
+        .join("src/bin")
+        .join(format!("{}.rs", bin_name(year, day)));
+    let input_path = input_dir.join(format!("{}.txt", name));
+    let year_path = year_dir.join("mod.rs");
+    let lib_path = root.join("src/lib.rs");
+    let solution_path = root.join("src/solution.rs");
+
+    let existing = [&module_path, &bin_path, &input_path]
+        .iter()
+        .filter(|path| path.exists())
+        .map(|path| format!("'{}'", path.display()))
+        .collect::<Vec<_>>();
+    if !existing.is_empty() {
+        bail!("Refusing to overwrite {}", existing.join(", "));
+    }
+
+    // A year's first day also creates its module, which `lib.rs` has to declare.
+    let lib = if year_path.exists() {
+        None
+    } else {
+        let lib = insert_sorted(
+            &read(&lib_path)?,
+            r"(?m)^pub mod y(\d+);$",
+            &[year.into()],
+            &format!("pub mod y{};", year),
+            "\n",
+        )
+        .with_context(|| format!("No year modules found in '{}'", lib_path.display()))?;
+
+        Some(lib)
+    };
+
+    let year_module = match &lib {
+        Some(_) => format!(
+            "//! The {} puzzles, a module per day.\n\npub mod {};\n",
+            year, name
+        ),
+        None => {
+            let year_module = read(&year_path)?;
+            if year_module.contains(&format!("mod {};", name)) {
+                bail!(
+                    "Day {} is already declared in '{}'",
+                    day,
+                    year_path.display()
+                );
+            }
+
+            insert_sorted(
+                &year_module,
+                r"(?m)^pub mod day(\d+);$",
+                &[day.into()],
+                &format!("pub mod {};", name),
+                "\n",
+            )
+            .with_context(|| format!("No day modules found in '{}'", year_path.display()))?
+        }
+    };
+
+    let solution = read(&solution_path)?;
+    let solution = insert_sorted(
+        &solution,
+        r"Registration::of::<crate::y(\d+)::day(\d+)::Day\d+>\(\),",
+        &[year.into(), day.into()],
+        &format!(
+            "Registration::of::<crate::y{}::{}::Day{:02}>(),",
+            year, name, day
+        ),
+        "\n    ",
+    )
+    .with_context(|| format!("No registered days found in '{}'", solution_path.display()))?;
+
+    fs::create_dir_all(&year_dir)
+        .with_context(|| format!("Failed to create '{}'", year_dir.display()))?;
+    fs::create_dir_all(&input_dir)
+        .with_context(|| format!("Failed to create '{}'", input_dir.display()))?;
+
+    create(&module_path, &module_template(year, day))?;
+    create(&bin_path, &bin_template(year, day))?;
+    create(&input_path, "")?;
+    write(&year_path, &year_module)?;
+    write(&solution_path, &solution)?;
+
+    let mut changed = vec![module_path, bin_path, input_path, year_path, solution_path];
+    if let Some(lib) = lib {
+        write(&lib_path, &lib)?;
+        changed.push(lib_path);
+    }
+
+    Ok(changed)
+}
+
+/// The name of the binary that runs just `year`'s `day`. The 2020 binaries came before any other
+/// year, so only other years' names include the year.
+fn bin_name(year: u16, day: u8) -> String {
+    match year {
+        DEFAULT_YEAR => format!("day{:02}", day),
+        _ => format!("y{}_day{:02}", year, day),
+    }
+}
+
+/// Inserts `entry` next to the entries matched by `pattern`, keeping them ordered by `key`, which
+/// is compared with the numbers captured by the pattern's groups. `None` if nothing matches.
+fn insert_sorted(
+    text: &str,
+    pattern: &str,
+    key: &[u32],
+    entry: &str,
+    separator: &str,
+) -> Option<String> {
+    let regex = Regex::new(pattern).unwrap();
+    let matches = regex
+        .captures_iter(text)
+        .map(|caps| {
+            let whole = caps.get(0).unwrap();
+            let other = caps
+                .iter()
+                .skip(1)
+                .map(|group| {
+                    group.map_or(u32::MAX, |group| group.as_str().parse().unwrap_or(u32::MAX))
+                })
+                .collect::<Vec<_>>();
+
+            (other, whole.start(), whole.end())
+        })
+        .collect::<Vec<_>>();
+
+    let mut text = text.to_owned();
+    match matches
+        .iter()
+        .rev()
+        .find(|(other, _, _)| other.as_slice() < key)
+    {
+        Some(&(_, _, end)) => text.insert_str(end, &format!("{}{}", separator, entry)),
+        None => {
+            let (_, start, _) = matches.first()?;
+            text.insert_str(*start, &format!("{}{}", entry, separator));
+        }
+    }
+
+    Some(text)
+}
+
+fn module_template(year: u16, day: u8) -> String {
+    format!(
+        r#"//! Day {day}.
+
+use crate::{{input_lines, solution::Solution}};
+use anyhow::{{bail, Context, Result}};
+
+/// The day {day} puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    const YEAR: u16 = {year};
+    const DAY: u8 = {day};
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse() -> Result<Self::Input> {{
+        input_lines(Self::YEAR, Self::DAY)?
+            .map(|line| line.context("Failed to read line"))
+            .collect()
+    }}
+
+    fn part1(_input: &Self::Input) -> Result<Self::Answer1> {{
+        bail!("Part 1 is not solved yet")
+    }}
+
+    fn part2(_input: &Self::Input) -> Result<Self::Answer2> {{
+        bail!("Part 2 is not solved yet")
+    }}
+}}
+
+examples! {{
+    Day{day:02};
+
+    example {{
+        input: "",
+        // part1: ,
+        // part2: ,
+    }}
+}}
+"#,
+        year = year,
+        day = day
+    )
+}
+
+fn bin_template(year: u16, day: u8) -> String {
+    format!(
+        r#"use anyhow::Result;
+use aoc_2020::solution::run_day;
+
+fn main() -> Result<()> {{
+    env_logger::init();
+
+    for result in run_day({}, {}, None)? {{
+        println!("{{}}", result.description);
+    }}
+
+    Ok(())
+}}
+"#,
+        year, day
+    )
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read '{}'", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("Failed to write '{}'", path.display()))
+}
+
+/// Like `write`, but fails rather than replacing an existing file.
+fn create(path: &Path, contents: &str) -> Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .with_context(|| format!("Failed to create '{}'", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const LIB: &str = "pub mod solution;\npub mod y2020;\n";
+    const YEAR: &str =
+        "//! The 2020 puzzles, a module per day.\n\npub mod day01;\npub mod day03;\n";
+    const SOLUTION: &str = "\
+pub static SOLUTIONS: &[Registration] = &[
+    Registration::of::<crate::y2020::day01::Day01>(),
+    Registration::of::<crate::y2020::day03::Day03>(),
+];
+";
+
+    /// A crate root with days 1 and 3 of 2020, and `solution` as its `solution.rs`.
+    fn scratch(name: &str, solution: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        for dir in ["src/bin", "src/input/2020", "src/y2020"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("src/y2020/mod.rs"), YEAR).unwrap();
+        fs::write(root.join("src/solution.rs"), solution).unwrap();
+
+        root
+    }
+
+    fn read(root: &Path, path: &str) -> String {
+        fs::read_to_string(root.join(path)).unwrap()
+    }
+
+    #[test]
+    fn inserts_days_in_order() {
+        let root = scratch("order", SOLUTION);
+
+        new_day(&root, 2020, 2).unwrap();
+        new_day(&root, 2021, 1).unwrap();
+
+        assert_eq!(
+            read(&root, "src/y2020/mod.rs"),
+            "//! The 2020 puzzles, a module per day.\n\npub mod day01;\npub mod day02;\npub mod day03;\n"
+        );
+        assert_eq!(
+            read(&root, "src/solution.rs"),
+            "\
+pub static SOLUTIONS: &[Registration] = &[
+    Registration::of::<crate::y2020::day01::Day01>(),
+    Registration::of::<crate::y2020::day02::Day02>(),
+    Registration::of::<crate::y2020::day03::Day03>(),
+    Registration::of::<crate::y2021::day01::Day01>(),
+];
+"
+        );
+        assert_eq!(
+            read(&root, "src/lib.rs"),
+            "pub mod solution;\npub mod y2020;\npub mod y2021;\n"
+        );
+        assert_eq!(
+            read(&root, "src/y2021/mod.rs"),
+            "//! The 2021 puzzles, a module per day.\n\npub mod day01;\n"
+        );
+
+        assert!(read(&root, "src/y2020/day02.rs").contains("const YEAR: u16 = 2020;"));
+        assert!(read(&root, "src/bin/day02.rs").contains("run_day(2020, 2, None)"));
+        assert!(read(&root, "src/y2021/day01.rs").contains("const YEAR: u16 = 2021;"));
+        assert!(read(&root, "src/bin/y2021_day01.rs").contains("run_day(2021, 1, None)"));
+        assert_eq!(read(&root, "src/input/2021/day01.txt"), "");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn refuses_existing_days() {
+        let root = scratch("existing", SOLUTION);
+        fs::write(root.join("src/y2020/day03.rs"), "// Solved").unwrap();
+
+        let error = new_day(&root, 2020, 3).unwrap_err();
+        assert!(error.to_string().starts_with("Refusing to overwrite"));
+        assert!(error.to_string().contains("day03.rs"));
+        assert_eq!(read(&root, "src/y2020/day03.rs"), "// Solved");
+
+        // Declared, but with none of the day's files.
+        let error = new_day(&root, 2020, 1).unwrap_err();
+        assert!(error.to_string().starts_with("Day 1 is already declared"));
+        assert!(!root.join("src/bin/day01.rs").exists());
+
+        assert_eq!(read(&root, "src/y2020/mod.rs"), YEAR);
+        assert_eq!(read(&root, "src/solution.rs"), SOLUTION);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn fails_without_registrations() {
+        let solution = "pub static SOLUTIONS: &[Registration] = &[];\n";
+        let root = scratch("anchor", solution);
+
+        let error = new_day(&root, 2020, 2).unwrap_err();
+        assert!(error.to_string().starts_with("No registered days found in"));
+
+        // Nothing is written when the registration can't be added.
+        assert!(!root.join("src/y2020/day02.rs").exists());
+        assert!(!root.join("src/bin/day02.rs").exists());
+        assert!(!root.join("src/input/2020/day02.txt").exists());
+        assert_eq!(read(&root, "src/y2020/mod.rs"), YEAR);
+        assert_eq!(read(&root, "src/solution.rs"), solution);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
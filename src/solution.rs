@@ -0,0 +1,291 @@
+use anyhow::{anyhow, bail, Error, Result};
+use log::debug;
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::bench::{self, BenchConfig, Phase, Timing};
+use crate::budget;
+use crate::generate::Rng;
+use crate::memory::{self, Profile};
+use crate::source::locate;
+
+/// A single day's puzzle, split into parsing the input and solving each of the two parts.
+pub trait Solution {
+    /// The Advent of Code year the puzzle is from.
+    const YEAR: u16;
+    /// The day of the advent calendar that this solves.
+    const DAY: u8;
+
+    /// The parsed puzzle input that both parts operate on.
+    type Input;
+
+    /// The answers to each part. Their `Display` output is the value the puzzle asks for.
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse() -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    /// Describes the answer to part 1 in a sentence for human readers.
+    fn describe1(answer: &Self::Answer1) -> String {
+        answer.to_string()
+    }
+
+    /// Describes the answer to part 2 in a sentence for human readers.
+    fn describe2(answer: &Self::Answer2) -> String {
+        answer.to_string()
+    }
+
+    /// Writes a random input that `parse` accepts and both parts can solve. `size` is roughly
+    /// the number of lines or records.
+    fn generate(_rng: &mut Rng, _size: usize) -> Result<String> {
+        bail!("Day {} has no input generator", Self::DAY)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Part::One => "1",
+            Part::Two => "2",
+        })
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(anyhow!("Part must be 1 or 2, got '{}'", s)),
+        }
+    }
+}
+
+/// The answer to one part of a day, along with how long the part took to run.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    /// The value the puzzle asks for, as it would be submitted.
+    pub answer: String,
+    /// The answer described in a sentence.
+    pub description: String,
+    /// Time spent in the part itself, excluding parsing.
+    pub elapsed: Duration,
+}
+
+impl PartResult {
+    /// The result as a tab-separated `<year> <day> <part> <answer>` line, which `parse_line` reads
+    /// back. Backslashes, tabs and newlines in the answer are escaped.
+    pub fn to_line(&self) -> String {
+        let mut answer = String::with_capacity(self.answer.len());
+        for c in self.answer.chars() {
+            match c {
+                '\\' => answer.push_str("\\\\"),
+                '\t' => answer.push_str("\\t"),
+                '\n' => answer.push_str("\\n"),
+                c => answer.push(c),
+            }
+        }
+
+        format!("{}\t{}\t{}\t{}", self.year, self.day, self.part, answer)
+    }
+
+    /// Reads a line written by `to_line`, returning the year, day, part and answer.
+    pub fn parse_line(line: &str) -> Result<(u16, u8, Part, String)> {
+        let (year, day, part, escaped) = match line.splitn(4, '\t').collect::<Vec<_>>()[..] {
+            [year, day, part, answer] => (year, day, part, answer),
+            _ => bail!(
+                "Expected '<year>\\t<day>\\t<part>\\t<answer>', got '{}'",
+                line
+            ),
+        };
+
+        let mut answer = String::with_capacity(escaped.len());
+        let mut chars = escaped.chars();
+        while let Some(c) = chars.next() {
+            answer.push(match c {
+                '\\' => match chars.next() {
+                    Some('\\') => '\\',
+                    Some('t') => '\t',
+                    Some('n') => '\n',
+                    _ => bail!("Invalid escape in answer '{}'", escaped),
+                },
+                c => c,
+            });
+        }
+
+        Ok((year.parse()?, day.parse()?, part.parse()?, answer))
+    }
+}
+
+/// Parses the input for `S` and then runs the requested part, or both parts if `part` is `None`.
+/// The run's time budget covers parsing as well as the parts.
+pub fn run<S: Solution>(part: Option<Part>) -> Result<Vec<PartResult>> {
+    run_with::<S>(part, |_, phase| phase())
+}
+
+/// Like `run`, but calls `around` with each phase, which must call the function it's given once.
+/// This lets callers such as `memory::profile` measure each phase on the same path as `run`.
+pub(crate) fn run_with<S: Solution>(
+    part: Option<Part>,
+    mut around: impl FnMut(Phase, &mut dyn FnMut()),
+) -> Result<Vec<PartResult>> {
+    budget::with_default_budget(|| {
+        debug!(
+            "Reading {} day {} input from {}",
+            S::YEAR,
+            S::DAY,
+            locate(S::YEAR, S::DAY)
+        );
+        let mut input = None;
+        around(Phase::Parse, &mut || input = Some(S::parse()));
+        let input = input.expect("The parse phase wasn't run")?;
+        let mut results = Vec::new();
+
+        if part != Some(Part::Two) {
+            let mut answer = None;
+            let start = Instant::now();
+            around(Phase::Part1, &mut || answer = Some(S::part1(&input)));
+            let elapsed = start.elapsed();
+            let answer = answer.expect("Part 1 wasn't run")?;
+
+            results.push(PartResult {
+                year: S::YEAR,
+                day: S::DAY,
+                part: Part::One,
+                answer: answer.to_string(),
+                description: S::describe1(&answer),
+                elapsed,
+            });
+        }
+
+        if part != Some(Part::One) {
+            let mut answer = None;
+            let start = Instant::now();
+            around(Phase::Part2, &mut || answer = Some(S::part2(&input)));
+            let elapsed = start.elapsed();
+            let answer = answer.expect("Part 2 wasn't run")?;
+
+            results.push(PartResult {
+                year: S::YEAR,
+                day: S::DAY,
+                part: Part::Two,
+                answer: answer.to_string(),
+                description: S::describe2(&answer),
+                elapsed,
+            });
+        }
+
+        Ok(results)
+    })
+}
+
+/// A type-erased handle to a `Solution` so that days can be looked up at runtime.
+pub struct Registration {
+    pub year: u16,
+    pub day: u8,
+    pub run: fn(Option<Part>) -> Result<Vec<PartResult>>,
+    pub bench: fn(&BenchConfig) -> Result<Vec<Timing>>,
+    pub generate: fn(&mut Rng, usize) -> Result<String>,
+    pub profile: fn(Option<Part>) -> Result<Profile>,
+}
+
+impl Registration {
+    const fn of<S: Solution>() -> Self {
+        Registration {
+            year: S::YEAR,
+            day: S::DAY,
+            run: run::<S>,
+            bench: bench::bench::<S>,
+            generate: S::generate,
+            profile: memory::profile::<S>,
+        }
+    }
+}
+
+/// The year that commands work on when none is given.
+pub const DEFAULT_YEAR: u16 = 2020;
+
+/// Every implemented day of every year, in order. Each year's `DayNN` types live in a module of
+/// their own, such as `crate::y2020`, so they're named by their full path here.
+pub static SOLUTIONS: &[Registration] = &[
+    Registration::of::<crate::y2020::day01::Day01>(),
+    Registration::of::<crate::y2020::day02::Day02>(),
+    Registration::of::<crate::y2020::day03::Day03>(),
+    Registration::of::<crate::y2020::day04::Day04>(),
+    Registration::of::<crate::y2020::day05::Day05>(),
+    Registration::of::<crate::y2020::day06::Day06>(),
+    Registration::of::<crate::y2020::day07::Day07>(),
+    Registration::of::<crate::y2020::day08::Day08>(),
+    Registration::of::<crate::y2020::day09::Day09>(),
+    Registration::of::<crate::y2020::day10::Day10>(),
+    Registration::of::<crate::y2020::day11::Day11>(),
+    Registration::of::<crate::y2020::day12::Day12>(),
+    Registration::of::<crate::y2020::day13::Day13>(),
+    Registration::of::<crate::y2020::day14::Day14>(),
+    Registration::of::<crate::y2020::day15::Day15>(),
+    Registration::of::<crate::y2020::day16::Day16>(),
+    Registration::of::<crate::y2020::day17::Day17>(),
+    Registration::of::<crate::y2020::day18::Day18>(),
+    Registration::of::<crate::y2020::day19::Day19>(),
+];
+
+/// Every implemented day of `year`, in order.
+pub fn days(year: u16) -> impl Iterator<Item = &'static Registration> {
+    SOLUTIONS.iter().filter(move |reg| reg.year == year)
+}
+
+pub fn find(year: u16, day: u8) -> Result<&'static Registration> {
+    days(year)
+        .find(|reg| reg.day == day)
+        .ok_or_else(|| anyhow!("No solution for {} day {}", year, day))
+}
+
+/// Runs the requested part(s) of a single day, looked up by year and number.
+pub fn run_day(year: u16, day: u8, part: Option<Part>) -> Result<Vec<PartResult>> {
+    (find(year, day)?.run)(part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn result_lines_round_trip() {
+        for answer in ["514579", "a\\b\tc\nd", "mxmxvkd,sqjhc,fvjkl", ""] {
+            let result = PartResult {
+                year: 2020,
+                day: 21,
+                part: Part::Two,
+                answer: answer.to_owned(),
+                description: String::new(),
+                elapsed: Duration::ZERO,
+            };
+
+            let line = result.to_line();
+            assert!(!line.contains('\n'));
+            assert_eq!(
+                PartResult::parse_line(&line).unwrap(),
+                (2020, 21, Part::Two, answer.to_owned())
+            );
+        }
+
+        assert!(PartResult::parse_line("2020\t1\t1").is_err());
+        assert!(PartResult::parse_line("2020\t1\t1\tbad\\x").is_err());
+    }
+}
@@ -0,0 +1,350 @@
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use memmap2::Mmap;
+use std::{
+    cell::RefCell,
+    convert::Infallible,
+    env,
+    fmt::{self, Display},
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    ops::Deref,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::RwLock,
+};
+
+/// Environment variable naming a directory to read inputs from instead of `./src/input`. Like the
+/// default, it holds a folder per year of `dayNN.txt` files, e.g. `2020/day07.txt`.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+const DEFAULT_INPUT_DIR: &str = "./src/input";
+
+#[cfg(feature = "embed-inputs")]
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+lazy_static! {
+    static ref SOURCE: RwLock<InputSource> = RwLock::new(InputSource::Default);
+}
+
+thread_local! {
+    static OVERRIDE: RefCell<Option<Location>> = const { RefCell::new(None) };
+}
+
+/// Where `input_lines` and friends read puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `YYYY/dayNN.txt` in the directory named by `AOC_INPUT_DIR`, falling back to `./src/input`.
+    /// With the `embed-inputs` feature the fallback is the copy compiled into the binary instead.
+    Default,
+    /// A single file, read whichever day is asked for.
+    File(PathBuf),
+    /// `dayNN.txt` in the given directory, whichever year is asked for.
+    Dir(PathBuf),
+    /// Standard input.
+    Stdin,
+}
+
+impl InputSource {
+    /// Works out where `year`'s `day` input comes from, taking `AOC_INPUT_DIR` into account.
+    pub fn resolve(&self, year: u16, day: u8) -> Location {
+        match self {
+            InputSource::Default => match env::var_os(INPUT_DIR_VAR) {
+                Some(_) => Location::File(input_path(year, day)),
+                None => default_location(year, day),
+            },
+            InputSource::File(path) => Location::File(path.clone()),
+            InputSource::Dir(dir) => Location::File(day_path(dir, day)),
+            InputSource::Stdin => Location::Stdin,
+        }
+    }
+
+    pub fn open(&self, year: u16, day: u8) -> Result<Box<dyn BufRead>> {
+        self.resolve(year, day).open()
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = Infallible;
+
+    /// `-` selects stdin, an existing directory selects `Dir` and anything else is treated as a file.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let path = PathBuf::from(s);
+
+        Ok(if s == "-" {
+            InputSource::Stdin
+        } else if path.is_dir() {
+            InputSource::Dir(path)
+        } else {
+            InputSource::File(path)
+        })
+    }
+}
+
+/// A single day's resolved input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Location {
+    File(PathBuf),
+    Stdin,
+    /// The copy of a year's day compiled into the binary by the `embed-inputs` feature.
+    Embedded(u16, u8),
+    /// Text held in memory, set up by `with_input_text`.
+    Text(String),
+}
+
+impl Location {
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            Location::File(path) => open_file(path),
+            Location::Stdin => Ok(Box::new(io::stdin().lock())),
+            Location::Embedded(year, day) => open_embedded(*year, *day),
+            Location::Text(text) => Ok(Box::new(io::Cursor::new(text.clone().into_bytes()))),
+        }
+    }
+
+    /// Reads the whole input at once. Regular files are memory-mapped rather than copied, which is
+    /// much cheaper for huge inputs; anything else, including files that can't be mapped such as
+    /// pipes, is read into memory.
+    ///
+    /// # Safety
+    ///
+    /// If the input is a file, nothing may truncate it while the returned `InputText` is alive:
+    /// reading a mapped page past the new end kills the process with `SIGBUS`. Rewriting the file
+    /// in place changes the text under any `&str` already borrowed from it. `aoc watch` edits are
+    /// exactly this, so only map inputs that nothing else is writing to.
+    pub unsafe fn map(&self) -> Result<InputText> {
+        let contents = match self {
+            Location::File(path) => map_file(path)?,
+            _ => {
+                let mut bytes = Vec::new();
+                self.open()?
+                    .read_to_end(&mut bytes)
+                    .with_context(|| format!("Failed to read {}", self))?;
+                Contents::Owned(bytes)
+            }
+        };
+
+        Ok(InputText { contents })
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::File(path) => write!(f, "{}", path.display()),
+            Location::Stdin => f.write_str("<stdin>"),
+            Location::Embedded(year, day) => write!(f, "<embedded {}/day{:02}.txt>", year, day),
+            Location::Text(_) => f.write_str("<text>"),
+        }
+    }
+}
+
+/// A whole input, borrowed from a memory-mapped file where possible, as made by `Location::map`.
+/// Derefs to the raw bytes; `lines()` gives each line as a `&str` without allocating.
+pub struct InputText {
+    contents: Contents,
+}
+
+enum Contents {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+impl InputText {
+    /// Each line without its `\n` or `\r\n` ending, as `str::lines` splits them. Lines are
+    /// checked to be UTF-8 as they're reached rather than once up front, so text borrowed from a
+    /// mapping is always checked just before it's used.
+    pub fn lines(&self) -> impl Iterator<Item = Result<&str>> {
+        self.split_inclusive(|&byte| byte == b'\n')
+            .enumerate()
+            .map(|(index, line)| {
+                let line = match line.strip_suffix(b"\n") {
+                    Some(line) => line.strip_suffix(b"\r").unwrap_or(line),
+                    None => line,
+                };
+
+                std::str::from_utf8(line)
+                    .with_context(|| format!("Line {} is not valid UTF-8", index + 1))
+            })
+    }
+}
+
+impl Deref for InputText {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match &self.contents {
+            Contents::Mapped(map) => map,
+            Contents::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// Sets the source used by every subsequent input read in this process.
+pub fn set_input_source(source: InputSource) {
+    *SOURCE.write().unwrap() = source;
+}
+
+pub fn input_source() -> InputSource {
+    SOURCE.read().unwrap().clone()
+}
+
+/// The file that holds `year`'s `day` input on disk: `YYYY/dayNN.txt` in the directory named by
+/// `AOC_INPUT_DIR`, or in `./src/input` if that isn't set.
+pub fn input_path(year: u16, day: u8) -> PathBuf {
+    let root = env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR));
+
+    day_path(&root.join(year.to_string()), day)
+}
+
+/// Where `year`'s `day` input is read from on the current thread.
+pub fn locate(year: u16, day: u8) -> Location {
+    OVERRIDE
+        .with(|location| location.borrow().clone())
+        .unwrap_or_else(|| input_source().resolve(year, day))
+}
+
+/// Runs `f` with every input read on the current thread returning `text`, whichever day is asked
+/// for. This is how examples go through the same parsing code as the real input.
+pub fn with_input_text<R>(text: &str, f: impl FnOnce() -> R) -> R {
+    with_location(Location::Text(text.to_owned()), f)
+}
+
+/// Runs `f` with every input read on the current thread coming from the file at `path`,
+/// whichever day is asked for. Unlike `set_input_source`, other threads are unaffected.
+pub fn with_input_file<R>(path: &Path, f: impl FnOnce() -> R) -> R {
+    with_location(Location::File(path.to_owned()), f)
+}
+
+fn with_location<R>(location: Location, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Location>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            OVERRIDE.with(|location| *location.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(OVERRIDE.with(|current| current.replace(Some(location))));
+
+    f()
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+fn default_location(year: u16, day: u8) -> Location {
+    Location::File(input_path(year, day))
+}
+
+#[cfg(feature = "embed-inputs")]
+fn default_location(year: u16, day: u8) -> Location {
+    Location::Embedded(year, day)
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+fn open_embedded(year: u16, day: u8) -> Result<Box<dyn BufRead>> {
+    anyhow::bail!(
+        "Can't read the embedded input for {} day {} without the embed-inputs feature",
+        year,
+        day
+    )
+}
+
+#[cfg(feature = "embed-inputs")]
+fn open_embedded(year: u16, day: u8) -> Result<Box<dyn BufRead>> {
+    let text = embedded_input(year, day).with_context(|| {
+        format!(
+            "No input for {} day {} was embedded at build time",
+            year, day
+        )
+    })?;
+
+    Ok(Box::new(io::Cursor::new(text.as_bytes())))
+}
+
+fn day_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{:0width$}.txt", day, width = 2))
+}
+
+/// Maps the file at `path`, or reads it into memory if it isn't a regular file or can't be
+/// mapped, as with the pipe behind `--input <(...)`.
+///
+/// # Safety
+///
+/// The same as `Location::map`: nothing may truncate or rewrite the file while it's mapped.
+unsafe fn map_file(path: &Path) -> Result<Contents> {
+    let mut file = File::open(path)
+        .with_context(|| format!("Failed to open input file '{}'", path.display()))?;
+    let is_file = file.metadata().is_ok_and(|metadata| metadata.is_file());
+
+    // SAFETY: the caller promises that nothing modifies the file while the mapping is alive.
+    match is_file.then(|| unsafe { Mmap::map(&file) }) {
+        Some(Ok(map)) => Ok(Contents::Mapped(map)),
+        _ => {
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes)
+                .with_context(|| format!("Failed to read input file '{}'", path.display()))?;
+
+            Ok(Contents::Owned(bytes))
+        }
+    }
+}
+
+fn open_file(path: &Path) -> Result<Box<dyn BufRead>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open input file '{}'", path.display()))?;
+
+    Ok(Box::new(BufReader::new(file)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn lines(text: &InputText) -> Vec<&str> {
+        text.lines().collect::<Result<_>>().unwrap()
+    }
+
+    // SAFETY: each test's file is private to it and isn't modified while mapped.
+
+    #[test]
+    fn maps_files_and_text() {
+        let path = env::temp_dir().join(format!("aoc-source-{}.txt", std::process::id()));
+        fs::write(&path, "1721\r\n979\n\n366").unwrap();
+
+        let text = unsafe { Location::File(path.clone()).map() }.unwrap();
+        assert_eq!(lines(&text), ["1721", "979", "", "366"]);
+        drop(text);
+        fs::remove_file(&path).unwrap();
+
+        let text = unsafe { Location::Text("a\nb\n".to_owned()).map() }.unwrap();
+        assert_eq!(lines(&text), ["a", "b"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reads_files_that_cannot_be_mapped() {
+        // Character devices can't be mapped, like the pipes that process substitution gives.
+        let text = unsafe { Location::File(PathBuf::from("/dev/null")).map() }.unwrap();
+        assert!(text.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_utf8() {
+        let path = env::temp_dir().join(format!("aoc-source-utf8-{}.txt", std::process::id()));
+        fs::write(&path, b"12\n\xff\n").unwrap();
+
+        let text = unsafe { Location::File(path.clone()).map() }.unwrap();
+        let mut lines = text.lines();
+        assert_eq!(lines.next().unwrap().unwrap(), "12");
+        assert_eq!(
+            lines.next().unwrap().unwrap_err().to_string(),
+            "Line 2 is not valid UTF-8"
+        );
+        drop(lines);
+        drop(text);
+        fs::remove_file(&path).unwrap();
+    }
+}
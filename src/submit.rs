@@ -0,0 +1,464 @@
+use anyhow::{bail, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{
+    fmt::{self, Display},
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::solution::Part;
+
+/// The default location of the local record of submitted answers, relative to the crate root.
+pub const DEFAULT_LOG_PATH: &str = "./.aoc-submissions";
+
+lazy_static! {
+    static ref TOO_SOON_REGEX: Regex =
+        Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    static ref RETRY_REGEX: Regex =
+        Regex::new(r"(?i)wait (one|\d+) minutes? before trying again").unwrap();
+}
+
+/// Which way a wrong answer was off, when the site says.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The site's response to a submitted answer.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    /// The answer was wrong, and no other answer is accepted until `wait` has passed.
+    Incorrect {
+        hint: Option<Hint>,
+        wait: Duration,
+    },
+    /// The answer wasn't checked because the last one was submitted too recently.
+    TooSoon {
+        wait: Duration,
+    },
+    /// The part has already been solved, or part 1 hasn't been solved yet.
+    WrongLevel,
+}
+
+impl Outcome {
+    /// Reads the outcome from the HTML page returned after submitting.
+    pub fn parse(page: &str) -> Result<Outcome> {
+        if page.contains("That's the right answer") {
+            return Ok(Outcome::Correct);
+        }
+
+        if page.contains("That's not the right answer") {
+            let hint = if page.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if page.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+
+            let minutes = match RETRY_REGEX.captures(page) {
+                Some(caps) if &caps[1] == "one" => 1,
+                Some(caps) => caps[1].parse::<u64>()?,
+                None => 1,
+            };
+
+            return Ok(Outcome::Incorrect {
+                hint,
+                wait: Duration::from_secs(minutes * 60),
+            });
+        }
+
+        if let Some(caps) = TOO_SOON_REGEX.captures(page) {
+            let minutes = caps.get(1).map_or(Ok(0), |m| m.as_str().parse::<u64>())?;
+            let seconds = caps[2].parse::<u64>()?;
+
+            return Ok(Outcome::TooSoon {
+                wait: Duration::from_secs(minutes * 60 + seconds),
+            });
+        }
+
+        if page.contains("You don't seem to be solving the right level") {
+            return Ok(Outcome::WrongLevel);
+        }
+
+        bail!("Unrecognised response to the submission")
+    }
+
+    /// How long to hold off before the next submission.
+    pub fn wait(&self) -> Duration {
+        match self {
+            Outcome::Incorrect { wait, .. } | Outcome::TooSoon { wait } => *wait,
+            Outcome::Correct | Outcome::WrongLevel => Duration::from_secs(0),
+        }
+    }
+
+    /// Whether the site actually checked the answer. Answers that it didn't check can be sent
+    /// again later.
+    pub fn was_judged(&self) -> bool {
+        !matches!(self, Outcome::TooSoon { .. } | Outcome::WrongLevel)
+    }
+
+    /// The outcome logged as `tag`, the inverse of `tag()`, with the wait that was logged with it.
+    fn from_tag(tag: &str, wait: Duration) -> Option<Outcome> {
+        Some(match tag {
+            "correct" => Outcome::Correct,
+            "too-high" => Outcome::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait,
+            },
+            "too-low" => Outcome::Incorrect {
+                hint: Some(Hint::TooLow),
+                wait,
+            },
+            "incorrect" => Outcome::Incorrect { hint: None, wait },
+            "too-soon" => Outcome::TooSoon { wait },
+            "wrong-level" => Outcome::WrongLevel,
+            _ => return None,
+        })
+    }
+
+    /// The word used for this outcome in the submission log.
+    fn tag(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Incorrect {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => "too-high",
+            Outcome::Incorrect {
+                hint: Some(Hint::TooLow),
+                ..
+            } => "too-low",
+            Outcome::Incorrect { hint: None, .. } => "incorrect",
+            Outcome::TooSoon { .. } => "too-soon",
+            Outcome::WrongLevel => "wrong-level",
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => f.write_str("That's the right answer"),
+            Outcome::Incorrect { hint, wait } => {
+                f.write_str("That's not the right answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => f.write_str(", it's too high")?,
+                    Some(Hint::TooLow) => f.write_str(", it's too low")?,
+                    None => {}
+                }
+                write!(f, ". Wait {}s before trying again", wait.as_secs())
+            }
+            Outcome::TooSoon { wait } => write!(
+                f,
+                "Submitted too soon, wait {}s before trying again",
+                wait.as_secs()
+            ),
+            Outcome::WrongLevel => f.write_str("That part is already solved or not unlocked"),
+        }
+    }
+}
+
+/// A single submission as recorded in the log.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    /// The `Outcome` tag, such as `correct` or `too-high`.
+    pub result: String,
+    /// When the answer was submitted.
+    pub at: SystemTime,
+    /// How long the site asked to wait afterwards.
+    pub wait: Duration,
+}
+
+impl Submission {
+    pub fn new(
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        outcome: &Outcome,
+        at: SystemTime,
+    ) -> Submission {
+        Submission {
+            year,
+            day,
+            part,
+            answer: answer.to_owned(),
+            result: outcome.tag().to_owned(),
+            at,
+            wait: outcome.wait(),
+        }
+    }
+
+    /// The site's response, if `result` is a tag this version knows.
+    pub fn outcome(&self) -> Option<Outcome> {
+        Outcome::from_tag(&self.result, self.wait)
+    }
+}
+
+/// Every answer submitted from this checkout, so that nothing is sent twice and cooldowns are
+/// respected between runs.
+///
+/// The file format is one `<year> <day> <part> <unix time> <wait seconds> <result> <answer>`
+/// entry per line. Blank lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct SubmissionLog {
+    submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Reads the log at `path`. A missing file is an empty log.
+    pub fn load(path: &Path) -> Result<SubmissionLog> {
+        if !path.exists() {
+            return Ok(SubmissionLog::default());
+        }
+
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read submission log '{}'", path.display()))?;
+
+        text.parse()
+            .with_context(|| format!("Invalid submission log '{}'", path.display()))
+    }
+
+    /// Why `answer` shouldn't be submitted for `year`'s `day` and `part` at `now`, if there is a
+    /// reason. The site's cooldown applies across every year and day.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+        now: SystemTime,
+    ) -> Option<String> {
+        let previous = self.submissions.iter().filter(|submission| {
+            submission.year == year && submission.day == day && submission.part == part
+        });
+
+        for submission in previous {
+            if submission.result == "correct" {
+                return Some(format!(
+                    "{} day {} part {} was already solved with {}",
+                    year, day, part, submission.answer
+                ));
+            }
+
+            // Unknown results are treated as judged, so that they're never resent.
+            let judged = submission
+                .outcome()
+                .is_none_or(|outcome| outcome.was_judged());
+            if submission.answer == answer && judged {
+                return Some(format!(
+                    "{} was already submitted for {} day {} part {} and was {}",
+                    answer, year, day, part, submission.result
+                ));
+            }
+        }
+
+        let ready = self
+            .submissions
+            .iter()
+            .map(|submission| submission.at + submission.wait)
+            .max()?;
+
+        ready.duration_since(now).ok().map(|remaining| {
+            format!(
+                "The site asked to wait before submitting again, {}s to go",
+                remaining.as_secs() + 1
+            )
+        })
+    }
+
+    /// Adds a submission to the log and appends it to the file at `path`.
+    pub fn record(&mut self, path: &Path, submission: Submission) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open submission log '{}'", path.display()))?;
+
+        writeln!(
+            file,
+            "{} {:02} {} {} {} {} {}",
+            submission.year,
+            submission.day,
+            submission.part,
+            submission
+                .at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            submission.wait.as_secs(),
+            submission.result,
+            submission.answer
+        )
+        .with_context(|| format!("Failed to write submission log '{}'", path.display()))?;
+
+        self.submissions.push(submission);
+
+        Ok(())
+    }
+}
+
+impl FromStr for SubmissionLog {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut submissions = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields = line.splitn(7, ' ').collect::<Vec<_>>();
+            let (year, day, part, at, wait, result, answer) = match fields.as_slice() {
+                [year, day, part, at, wait, result, answer] => {
+                    (year, day, part, at, wait, result, answer)
+                }
+                _ => bail!(
+                    "Line {} should be '<year> <day> <part> <unix time> <wait seconds> <result> <answer>': '{}'",
+                    index + 1,
+                    line
+                ),
+            };
+
+            let number = |field: &str| {
+                field
+                    .parse::<u64>()
+                    .with_context(|| format!("Invalid number on line {}: '{}'", index + 1, field))
+            };
+
+            submissions.push(Submission {
+                year: year
+                    .parse::<u16>()
+                    .with_context(|| format!("Invalid year on line {}: '{}'", index + 1, year))?,
+                day: day
+                    .parse::<u8>()
+                    .with_context(|| format!("Invalid day on line {}: '{}'", index + 1, day))?,
+                part: part
+                    .parse::<Part>()
+                    .with_context(|| format!("Invalid part on line {}", index + 1))?,
+                answer: answer.to_string(),
+                result: result.to_string(),
+                at: UNIX_EPOCH + Duration::from_secs(number(at)?),
+                wait: Duration::from_secs(number(wait)?),
+            });
+        }
+
+        Ok(SubmissionLog { submissions })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(
+            Outcome::parse("<p>That's the right answer!  You are one gold star closer.</p>")
+                .unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::parse(
+                "<p>That's not the right answer; your answer is too high.  Please wait one \
+                 minute before trying again.</p>"
+            )
+            .unwrap(),
+            Outcome::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait: Duration::from_secs(60)
+            }
+        );
+        assert_eq!(
+            Outcome::parse(
+                "<p>That's not the right answer.  please wait 5 minutes before trying again.</p>"
+            )
+            .unwrap(),
+            Outcome::Incorrect {
+                hint: None,
+                wait: Duration::from_secs(300)
+            }
+        );
+        assert_eq!(
+            Outcome::parse(
+                "<p>You gave an answer too recently; you have to wait after submitting an \
+                 answer before trying again.  You have 1m 5s left to wait.</p>"
+            )
+            .unwrap(),
+            Outcome::TooSoon {
+                wait: Duration::from_secs(65)
+            }
+        );
+        assert_eq!(
+            Outcome::parse("<p>You don't seem to be solving the right level.</p>").unwrap(),
+            Outcome::WrongLevel
+        );
+        assert!(Outcome::parse("<p>Something else</p>").is_err());
+    }
+
+    #[test]
+    fn tags_round_trip() {
+        let wait = Duration::from_secs(60);
+        for outcome in [
+            Outcome::Correct,
+            Outcome::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait,
+            },
+            Outcome::Incorrect {
+                hint: Some(Hint::TooLow),
+                wait,
+            },
+            Outcome::Incorrect { hint: None, wait },
+            Outcome::TooSoon { wait },
+            Outcome::WrongLevel,
+        ] {
+            assert_eq!(Outcome::from_tag(outcome.tag(), wait), Some(outcome));
+        }
+    }
+
+    #[test]
+    fn refuses_repeats_and_cooldowns() {
+        let log = "\
+            # year day part at wait result answer\n\
+            2020 01 1 1000 60 too-low 12\n\
+            2020 01 2 2000 0 too-soon 99\n\
+            2019 02 1 3000 0 correct 7\n\
+            2020 03 2 4000 0 wrong-level 55\n"
+            .parse::<SubmissionLog>()
+            .unwrap();
+        let later = UNIX_EPOCH + Duration::from_secs(5000);
+
+        assert!(log.check(2020, 1, Part::One, "12", later).is_some());
+        assert!(log.check(2020, 1, Part::One, "13", later).is_none());
+        assert!(log.check(2020, 1, Part::Two, "99", later).is_none());
+        assert!(log.check(2019, 1, Part::One, "12", later).is_none());
+        assert!(log.check(2019, 2, Part::One, "8", later).is_some());
+        assert!(log.check(2020, 2, Part::One, "8", later).is_none());
+        assert!(log.check(2020, 3, Part::Two, "55", later).is_none());
+        assert!(log
+            .check(
+                2020,
+                1,
+                Part::One,
+                "13",
+                UNIX_EPOCH + Duration::from_secs(1030)
+            )
+            .is_some());
+
+        assert!("01 1 1000 60 too-low 12".parse::<SubmissionLog>().is_err());
+    }
+}
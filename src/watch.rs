@@ -0,0 +1,166 @@
+use anyhow::{bail, Context, Result};
+use lazy_static::lazy_static;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use regex::Regex;
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    time::Duration,
+};
+
+use crate::{
+    solution::{Part, PartResult},
+    source::input_path,
+};
+
+/// How long to keep collecting events after the first one, so that an editor's burst of writes
+/// triggers a single rebuild.
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+lazy_static! {
+    static ref DAY_FILE_REGEX: Regex = Regex::new(r"^day(\d+)\.(rs|txt)$").unwrap();
+}
+
+/// Filesystem notifications for everything that can change a day's answers: the crate's sources
+/// and the day's input file.
+pub struct DayWatcher {
+    day: u8,
+    events: Receiver<notify::Result<Event>>,
+    // Dropping the watcher stops the notifications.
+    _watcher: RecommendedWatcher,
+}
+
+impl DayWatcher {
+    pub fn new(year: u16, day: u8) -> Result<DayWatcher> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(sender).context("Failed to start watching files")?;
+
+        watcher
+            .watch(Path::new("src"), RecursiveMode::Recursive)
+            .context("Failed to watch 'src'")?;
+
+        // The input may live outside `src` when `AOC_INPUT_DIR` is set. Its directory is watched
+        // rather than the file so that editors which replace the file are still noticed.
+        let input_dir = input_path(year, day)
+            .parent()
+            .map(Path::to_owned)
+            .unwrap_or_default();
+        if !input_dir.starts_with("src") && !input_dir.starts_with("./src") {
+            watcher
+                .watch(&input_dir, RecursiveMode::NonRecursive)
+                .with_context(|| format!("Failed to watch '{}'", input_dir.display()))?;
+        }
+
+        Ok(DayWatcher {
+            day,
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    /// Blocks until a file relevant to the day changes, returning the changed files.
+    pub fn wait(&self) -> Result<Vec<PathBuf>> {
+        loop {
+            let mut changed = self.relevant(self.events.recv()?)?;
+
+            while let Ok(event) = self.events.recv_timeout(SETTLE_TIME) {
+                for path in self.relevant(event)? {
+                    if !changed.contains(&path) {
+                        changed.push(path);
+                    }
+                }
+            }
+
+            if !changed.is_empty() {
+                return Ok(changed);
+            }
+        }
+    }
+
+    fn relevant(&self, event: notify::Result<Event>) -> Result<Vec<PathBuf>> {
+        let event = event.context("File watching failed")?;
+        if event.kind.is_access() {
+            return Ok(Vec::new());
+        }
+
+        Ok(event
+            .paths
+            .into_iter()
+            .filter(|path| is_relevant(path, self.day))
+            .collect())
+    }
+}
+
+/// Whether a change to `path` can affect `day`'s answers. Other days' files are ignored, but any
+/// other Rust source might be shared code.
+pub fn is_relevant(path: &Path, day: u8) -> bool {
+    let name = match path.file_name().and_then(|name| name.to_str()) {
+        Some(name) => name,
+        None => return false,
+    };
+
+    match DAY_FILE_REGEX.captures(name) {
+        Some(caps) => caps[1].parse::<u8>() == Ok(day),
+        None => name.ends_with(".rs"),
+    }
+}
+
+/// Rebuilds the crate and runs `year`'s `day` in a child process, returning each part's answer.
+/// Build errors are shown on stderr as cargo prints them.
+pub fn rebuild_and_run(year: u16, day: u8) -> Result<Vec<(Part, String)>> {
+    // Cargo sets `CARGO` for the processes it runs, which keeps the same toolchain.
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+    let output = Command::new(cargo)
+        .args(["run", "--quiet", "--bin", "aoc", "--", "run"])
+        .arg(day.to_string())
+        .args(["--year", &year.to_string()])
+        .args(["--format", "tsv"])
+        .stderr(Stdio::inherit())
+        .output()
+        .context("Failed to run cargo")?;
+
+    if !output.status.success() {
+        bail!("Day {} failed to build or run", day);
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            let (_, _, part, answer) = PartResult::parse_line(line)?;
+            Ok((part, answer))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relevant_files() {
+        assert!(is_relevant(Path::new("src/y2020/day07.rs"), 7));
+        assert!(is_relevant(Path::new("src/input/2020/day07.txt"), 7));
+        assert!(!is_relevant(Path::new("src/y2020/day08.rs"), 7));
+        assert!(!is_relevant(Path::new("src/input/2020/day08.txt"), 7));
+        assert!(is_relevant(Path::new("src/lib.rs"), 7));
+
+        assert!(!is_relevant(Path::new("src/day07"), 7));
+        assert!(!is_relevant(Path::new("src/.day07.rs.swp"), 7));
+        assert!(!is_relevant(Path::new("src/"), 7));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn ignores_names_that_arent_utf8() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        assert!(!is_relevant(
+            Path::new(OsStr::from_bytes(b"src/\xffday07.rs")),
+            7
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+//! Day 1: Report Repair. Finds the expense report entries that sum to 2020.
+
+use crate::{
+    generate::{lines, Rng},
+    input_parsed,
+    solution::Solution,
+};
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use std::fmt::{self, Display};
+
+/// The day 1 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 1;
+    type Input = Vec<i64>;
+    type Answer1 = Entries;
+    type Answer2 = Entries;
+
+    fn parse() -> Result<Self::Input> {
+        input_parsed(Self::YEAR, Self::DAY)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        part1(input).context("No two entries sum to 2020")
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        part2(input).context("No three entries sum to 2020")
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Found {} = {}", answer.0.iter().join(" * "), answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Found {} = {}", answer.0.iter().join(" * "), answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+/// The expense report entries that sum to 2020. The answer is their product.
+pub struct Entries(Vec<i64>);
+
+impl Entries {
+    pub fn values(&self) -> &[i64] {
+        &self.0
+    }
+}
+
+impl Display for Entries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.iter().product::<i64>())
+    }
+}
+
+fn part1(input: &[i64]) -> Option<Entries> {
+    for x in input {
+        for y in input {
+            if x + y == 2020 {
+                return Some(Entries(vec![*x, *y]));
+            }
+        }
+    }
+
+    None
+}
+
+fn part2(input: &[i64]) -> Option<Entries> {
+    for x in input {
+        for y in input {
+            for z in input {
+                if x + y + z == 2020 {
+                    return Some(Entries(vec![*x, *y, *z]));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Expense entries where exactly one pair and one triple sum to 2020. The rest are all more than
+/// half of 2020 and avoid the values that would complete another pair or triple.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let (pair, triple) = loop {
+        let x = rng.range(12..1000);
+        let y = rng.range(1011 - x..1000);
+        let triple = [x, y, 2020 - x - y];
+        let pair = rng.range(1..1010);
+
+        // No other pair or triple may sum to 2020, even if it repeats one of the values.
+        let special = [pair, 2020 - pair, x, y, triple[2]];
+        let sums = |n| {
+            special
+                .iter()
+                .combinations_with_replacement(n)
+                .filter(|entries| entries.iter().copied().sum::<usize>() == 2020)
+                .count()
+        };
+        if special.iter().unique().count() == special.len() && sums(2) == 1 && sums(3) == 1 {
+            break (pair, triple);
+        }
+    };
+
+    // Two fillers already sum to more than 2020, so only the small values can combine with one.
+    let small = [pair, triple[0], triple[1], triple[2]];
+    let mut forbidden = small.iter().map(|n| 2020 - n).collect::<Vec<usize>>();
+    for (a, b) in small.iter().tuple_combinations() {
+        forbidden.push(2020 - a - b);
+    }
+    for a in &small {
+        forbidden.push(2020 - 2 * a);
+    }
+
+    let mut entries = small.to_vec();
+    entries.push(2020 - pair);
+    while entries.len() < size {
+        let entry = rng.range(1011..2020);
+        if !forbidden.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+
+    rng.shuffle(&mut entries);
+    lines(entries.iter().map(|entry| entry.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The generator used to allow a filler of `2020 - 2a`, which made a triple with two copies
+    /// of a small entry.
+    #[test]
+    fn generated_answers() {
+        for seed in 0..8 {
+            let mut rng = Rng::new(seed);
+            let input = generate(&mut rng, 300)
+                .lines()
+                .map(|line| line.parse().unwrap())
+                .collect::<Vec<i64>>();
+
+            let small = input
+                .iter()
+                .filter(|n| **n < 1010)
+                .sorted()
+                .collect::<Vec<_>>();
+            assert_eq!(small.len(), 4);
+
+            let pair = part1(&input).unwrap();
+            let triple = part2(&input).unwrap();
+            let smaller = *pair.values().iter().min().unwrap();
+            assert!(small.contains(&&smaller));
+            assert_eq!(pair.values().iter().sum::<i64>(), 2020);
+
+            let mut expected = small.into_iter().copied().collect::<Vec<_>>();
+            expected.retain(|n| *n != smaller);
+            assert_eq!(
+                triple.values().iter().sorted().collect::<Vec<_>>(),
+                expected.iter().collect::<Vec<_>>()
+            );
+        }
+    }
+}
+
+examples! {
+    Day01;
+
+    expense_report {
+        input: "\
+            1721\n\
+            979\n\
+            366\n\
+            299\n\
+            675\n\
+            1456",
+        part1: 514579,
+        part2: 241861950,
+    }
+}
@@ -0,0 +1,147 @@
+//! Day 2: Password Philosophy. Checks passwords against the policies they were set under.
+
+use crate::{
+    generate::{lines, Rng},
+    input_parsed, parse_field,
+    solution::Solution,
+    ParseError,
+};
+use anyhow::Result;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::str::FromStr;
+
+/// The day 2 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 2;
+    type Input = Vec<Policy>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse() -> Result<Self::Input> {
+        input_parsed(Self::YEAR, Self::DAY)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("There are {} valid passwords with first policy!", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("There are {} valid passwords with second policy!", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(policies: &[Policy]) -> usize {
+    policies
+        .iter()
+        .filter(|policy| Policy::is_valid_part_1(policy))
+        .count()
+}
+
+fn part2(policies: &[Policy]) -> usize {
+    policies
+        .iter()
+        .filter(|policy| Policy::is_valid_part_2(policy))
+        .count()
+}
+
+/// A password and the policy it was set under, parsed from lines like `1-3 a: abcde`.
+pub struct Policy {
+    min: usize,
+    max: usize,
+    target_char: char,
+    password: String,
+}
+
+impl Policy {
+    /// Whether the password has between `min` and `max` of the target character.
+    pub fn is_valid_part_1(&self) -> bool {
+        let count = self.password.matches(self.target_char).count();
+        count >= self.min && self.max >= count
+    }
+
+    /// Whether exactly one of the 1-based positions `min` and `max` holds the target character.
+    pub fn is_valid_part_2(&self) -> bool {
+        let at = |position: usize| {
+            position
+                .checked_sub(1)
+                .and_then(|index| self.password.chars().nth(index))
+        };
+
+        [at(self.min), at(self.max)]
+            .iter()
+            .filter(|opt| **opt == Some(self.target_char))
+            .count()
+            == 1
+    }
+}
+
+impl FromStr for Policy {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"^([^-]*)-(\S*) (\S): (.*)$").unwrap();
+        }
+
+        let caps = RE
+            .captures(s)
+            .ok_or_else(|| ParseError::whole(s, "Expected a policy like '1-3 a: password'"))?;
+
+        Ok(Policy {
+            min: parse_field(s, &caps[1], "minimum")?,
+            max: parse_field(s, &caps[2], "maximum")?,
+            target_char: caps[3].chars().next().unwrap(),
+            password: caps[4].to_string(),
+        })
+    }
+}
+
+/// Policies with passwords around the policy's length, where the letter is common but not certain.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let min = rng.range(1..10);
+        let max = rng.range(min + 1..min + 10);
+        let letter = rng.letter();
+        let length = rng.range(max.saturating_sub(4).max(1)..max + 4);
+        let password = (0..length)
+            .map(|_| {
+                if rng.chance(0.4) {
+                    letter
+                } else {
+                    rng.letter()
+                }
+            })
+            .collect::<String>();
+
+        format!("{}-{} {}: {}", min, max, letter, password)
+    }))
+}
+
+examples! {
+    Day02;
+
+    passwords {
+        input: "\
+            1-3 a: abcde\n\
+            1-3 b: cdefg\n\
+            2-9 c: ccccccccc",
+        part1: 2,
+        part2: 1,
+    }
+}
@@ -0,0 +1,114 @@
+//! Day 3: Toboggan Trajectory. Counts the trees hit sledding down a repeating map.
+
+use crate::{
+    generate::{lines, Rng},
+    input_grid,
+    solution::Solution,
+    Grid,
+};
+use anyhow::Result;
+
+/// The day 3 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 3;
+    type Input = Grid<bool>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse() -> Result<Self::Input> {
+        input_grid(Self::YEAR, Self::DAY, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Hit {} trees", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Combined the totals: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(input: &Grid<bool>) -> u64 {
+    count_trees(input, 3, 1)
+}
+
+fn part2(input: &Grid<bool>) -> u64 {
+    [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+        .iter()
+        .fold(1, |total, slope| {
+            total * count_trees(input, slope.0, slope.1)
+        })
+}
+
+/// Counts the trees (`true` cells) hit going from the top-left corner of `map` to the bottom,
+/// moving `right` and `down` at each step. The map repeats to the right.
+///
+/// Panics if `down` is 0, as the bottom would never be reached.
+pub fn count_trees(map: &Grid<bool>, right: usize, down: usize) -> u64 {
+    assert!(down > 0, "The slope must move down");
+
+    let mut x = 0;
+    let mut y = 0;
+    let mut count = 0;
+
+    while y + down < map.height() {
+        y += down;
+        x += right;
+
+        // The map repeats infinitely to the right.
+        if *map.get_wrapping(x as isize, y as isize) {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+/// A slope 31 squares wide, like the real input, and `size` rows long.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size.max(1)).map(|_| {
+        (0..31)
+            .map(|_| if rng.chance(0.2) { '#' } else { '.' })
+            .collect::<String>()
+    }))
+}
+
+examples! {
+    Day03;
+
+    slopes {
+        input: "\
+            ..##.......\n\
+            #...#...#..\n\
+            .#....#..#.\n\
+            ..#.#...#.#\n\
+            .#...##..#.\n\
+            ..#.##.....\n\
+            .#.#.#....#\n\
+            .#........#\n\
+            #.##...#...\n\
+            #...##....#\n\
+            .#..#...#.#",
+        part1: 7,
+        part2: 336,
+    }
+}
@@ -0,0 +1,337 @@
+//! Day 4: Passport Processing. Validates passports made of loosely formatted fields.
+
+use crate::{
+    generate::{lines, Rng},
+    input_records,
+    solution::Solution,
+    ParseError,
+};
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use log::warn;
+use regex::Regex;
+use std::ops::Range;
+
+/// The day 4 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 4;
+    type Input = Vec<Passport>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse() -> Result<Self::Input> {
+        input_records(Self::YEAR, Self::DAY)?
+            .map(|record| {
+                let record = record.context("Failed to read record")?;
+                let mut passport = Passport::default();
+
+                for (index, line) in record.iter().enumerate() {
+                    passport
+                        .merge_data(line)
+                        .map_err(|e| e.with_line(record.line_number(index)))?;
+                }
+
+                Ok(passport)
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("There are {} valid passports.", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("There are {} valid passports.", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(passports: &[Passport]) -> usize {
+    passports.iter().filter(|pp| pp.is_valid()).count()
+}
+
+fn part2(passports: &[Passport]) -> usize {
+    passports
+        .iter()
+        .filter(|pp| pp.is_valid_data_checked())
+        .count()
+}
+
+/// A passport's fields, gathered from one or more lines of `key:value` pairs. The country ID
+/// (`cid`) is optional and its value is ignored.
+#[derive(Default, Debug)]
+pub struct Passport {
+    birth_year: Option<String>,
+    issue_year: Option<String>,
+    exp_year: Option<String>,
+    hgt: Option<String>,
+    hcl: Option<String>,
+    ecl: Option<String>,
+    pid: Option<String>,
+    cid: Option<()>,
+}
+
+impl Passport {
+    /// Adds the fields on `line`, which are separated by single spaces. Later values replace
+    /// earlier ones.
+    pub fn merge_data(&mut self, line: &str) -> Result<(), ParseError> {
+        for field in line.split(' ') {
+            let (part, value) = field
+                .split_once(':')
+                .ok_or_else(|| ParseError::at(line, field, "Expected a 'key:value' field"))?;
+
+            match part {
+                "byr" => self.birth_year = Some(value.to_owned()),
+                "iyr" => self.issue_year = Some(value.to_owned()),
+                "eyr" => self.exp_year = Some(value.to_owned()),
+                "hgt" => self.hgt = Some(value.to_owned()),
+                "hcl" => self.hcl = Some(value.to_owned()),
+                "ecl" => self.ecl = Some(value.to_owned()),
+                "pid" => self.pid = Some(value.to_owned()),
+                "cid" => self.cid = Some(()),
+                _ => warn!("Unknown passport data key '{}' in '{}'", part, line),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether every field other than `cid` is present.
+    pub fn is_valid(&self) -> bool {
+        let mandatory_fields = [
+            &self.birth_year,
+            &self.issue_year,
+            &self.exp_year,
+            &self.hgt,
+            &self.hcl,
+            &self.ecl,
+            &self.pid,
+        ];
+
+        !mandatory_fields.iter().any(|opt| opt.is_none())
+    }
+
+    /// Whether every field other than `cid` is present and has a valid value.
+    pub fn is_valid_data_checked(&self) -> bool {
+        if !self.is_valid() {
+            return false;
+        }
+
+        let byr = self.birth_year.as_ref().unwrap().parse::<usize>();
+        let is_okay = matches!(byr, Ok(val) if (1920..=2002).contains(&val));
+
+        if !is_okay {
+            return false;
+        }
+
+        let iyr = self.issue_year.as_ref().unwrap().parse::<usize>();
+        let is_okay = matches!(iyr, Ok(val) if (2010..=2020).contains(&val));
+
+        if !is_okay {
+            return false;
+        }
+
+        let eyr = self.exp_year.as_ref().unwrap().parse::<usize>();
+        let is_okay = matches!(eyr, Ok(val) if (2020..=2030).contains(&val));
+
+        if !is_okay {
+            return false;
+        }
+
+        let hgt = self.hgt.as_ref().unwrap();
+        lazy_static! {
+            static ref HGT_REGEX: Regex = Regex::new(r"([0-9]+)(in|cm)").unwrap();
+        }
+
+        if let Some(caps) = HGT_REGEX.captures(hgt) {
+            let value = caps.get(1).unwrap().as_str().parse::<usize>();
+            let units = caps.get(2).unwrap().as_str();
+
+            let is_valid = match (value, units) {
+                (Ok(value), "cm") => (150..=193).contains(&value),
+                (Ok(value), _) => (59..=76).contains(&value),
+                (Err(_), _) => false,
+            };
+
+            if !is_valid {
+                return false;
+            }
+        } else {
+            return false;
+        }
+
+        let hcl = self.hcl.as_ref().unwrap();
+        lazy_static! {
+            static ref HCL_REGEX: Regex = Regex::new(r"^#(([0-9]|[a-f]){6})$").unwrap();
+        }
+
+        if !HCL_REGEX.is_match(hcl) {
+            return false;
+        }
+
+        let ecl = self.ecl.as_ref().unwrap();
+        let is_okay = matches!(
+            ecl.as_str(),
+            "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth"
+        );
+
+        if !is_okay {
+            return false;
+        }
+
+        let pid = self.pid.as_ref().unwrap();
+        lazy_static! {
+            static ref PID_REGEX: Regex = Regex::new(r"(^[0-9]{9})$").unwrap();
+        }
+
+        PID_REGEX.is_match(pid)
+    }
+}
+
+/// Passports with their fields in a random order over one to four lines. Some are missing a
+/// required field, and some have values that fail part 2's checks.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const EYE_COLOURS: &[&str] = &["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+    fn year(rng: &mut Rng, key: &str, valid: Range<usize>) -> String {
+        let year = if rng.chance(0.9) {
+            rng.range(valid)
+        } else if rng.chance(0.5) {
+            rng.range(valid.start - 20..valid.start)
+        } else {
+            rng.range(valid.end..valid.end + 20)
+        };
+
+        format!("{}:{}", key, year)
+    }
+
+    let passports = (0..size).map(|_| {
+        let valid = rng.chance(0.9);
+        let height = match (rng.chance(0.5), valid) {
+            (true, true) => format!("{}cm", rng.range(150..194)),
+            (false, true) => format!("{}in", rng.range(59..77)),
+            (true, false) => format!("{}cm", rng.range(100..150)),
+            (false, false) => rng.range(59..194).to_string(),
+        };
+
+        let valid = rng.chance(0.9);
+        let hair = if valid {
+            format!("#{:06x}", rng.range(0..1 << 24))
+        } else {
+            format!("{:06x}", rng.range(0..1 << 24))
+        };
+
+        let eyes = if rng.chance(0.9) {
+            rng.pick(EYE_COLOURS)
+        } else {
+            rng.pick(&["gmt", "xry", "zzz"])
+        };
+
+        let id = if rng.chance(0.9) {
+            format!("{:09}", rng.range(0..1_000_000_000))
+        } else {
+            format!("{:010}", rng.range(0..10_000_000_000))
+        };
+
+        let mut fields = vec![
+            year(rng, "byr", 1920..2003),
+            year(rng, "iyr", 2010..2021),
+            year(rng, "eyr", 2020..2031),
+            format!("hgt:{}", height),
+            format!("hcl:{}", hair),
+            format!("ecl:{}", eyes),
+            format!("pid:{}", id),
+        ];
+
+        if rng.chance(0.2) {
+            fields.remove(rng.range(0..fields.len()));
+        }
+
+        if rng.chance(0.5) {
+            fields.push(format!("cid:{}", rng.range(100..350)));
+        }
+
+        rng.shuffle(&mut fields);
+
+        let mut passport = fields[0].clone();
+        for field in &fields[1..] {
+            passport.push(if rng.chance(0.3) { '\n' } else { ' ' });
+            passport.push_str(field);
+        }
+
+        passport
+    });
+
+    lines([passports.collect::<Vec<_>>().join("\n\n")])
+}
+
+examples! {
+    Day04;
+
+    required_fields {
+        input: "\
+            ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\n\
+            byr:1937 iyr:2017 cid:147 hgt:183cm\n\
+            \n\
+            iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884\n\
+            hcl:#cfa07d byr:1929\n\
+            \n\
+            hcl:#ae17e1 iyr:2013\n\
+            eyr:2024\n\
+            ecl:brn pid:760753108 byr:1931\n\
+            hgt:179cm\n\
+            \n\
+            hcl:#cfa07d eyr:2025 pid:166559648\n\
+            iyr:2011 ecl:brn hgt:59in",
+        part1: 2,
+    }
+
+    invalid_values {
+        input: "\
+            eyr:1972 cid:100\n\
+            hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926\n\
+            \n\
+            iyr:2019\n\
+            hcl:#602927 eyr:1967 hgt:170cm\n\
+            ecl:grn pid:012533040 byr:1946\n\
+            \n\
+            hcl:dab227 iyr:2012\n\
+            ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277\n\
+            \n\
+            hgt:59cm ecl:zzz\n\
+            eyr:2038 hcl:74454a iyr:2023\n\
+            pid:3556412378 byr:2007",
+        part2: 0,
+    }
+
+    valid_values {
+        input: "\
+            pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\n\
+            hcl:#623a2f\n\
+            \n\
+            eyr:2029 ecl:blu cid:129 byr:1989\n\
+            iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm\n\
+            \n\
+            hcl:#888785\n\
+            hgt:164cm byr:2001 iyr:2015 cid:88\n\
+            pid:545766238 ecl:hzl\n\
+            eyr:2022\n\
+            \n\
+            iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719",
+        part2: 4,
+    }
+}
@@ -0,0 +1,219 @@
+//! Day 5: Binary Boarding. Decodes binary space partitioned boarding passes.
+
+use crate::{
+    generate::{lines, Rng},
+    input_parsed,
+    solution::Solution,
+    ParseError,
+};
+use anyhow::{bail, Context, Result};
+use log::debug;
+use std::{ops::Range, str::FromStr};
+
+/// The day 5 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 5;
+    type Input = Vec<SeatId>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse() -> Result<Self::Input> {
+        input_parsed(Self::YEAR, Self::DAY)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        part1(input).context("There are no boarding passes")
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        part2(input).context("No free seat has occupied seats either side")
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Maximum seat ID: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Your seat ID: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        generate(rng, size)
+    }
+}
+
+fn part1(input: &[SeatId]) -> Option<i32> {
+    input.iter().map(|id| id.seat_id()).max()
+}
+
+fn part2(input: &[SeatId]) -> Option<i32> {
+    let mut ids = input.iter().map(|id| id.seat_id()).collect::<Vec<i32>>();
+    ids.sort();
+
+    let mut expected_id = 0;
+
+    for id in &ids {
+        if expected_id != *id {
+            while expected_id != *id {
+                debug!("Missing ID: {}", expected_id);
+                expected_id += 1;
+            }
+        }
+
+        expected_id += 1;
+    }
+
+    // Seats at the very front and back don't exist, but ours has taken seats on either side.
+    ids.windows(2)
+        .find(|pair| pair[1] - pair[0] == 2)
+        .map(|pair| pair[0] + 1)
+}
+
+/// A boarding pass like `FBFBBFFRLR`. The first seven letters pick one of 128 rows and the last
+/// three one of 8 columns.
+pub struct SeatId {
+    row_id: i32,
+    column_id: i32,
+}
+
+impl SeatId {
+    pub fn row(&self) -> i32 {
+        self.row_id
+    }
+
+    pub fn column(&self) -> i32 {
+        self.column_id
+    }
+
+    /// The row times 8, plus the column.
+    pub fn seat_id(&self) -> i32 {
+        self.row_id * 8 + self.column_id
+    }
+}
+
+impl FromStr for SeatId {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        for (index, c) in s.char_indices().take(10) {
+            let (lower, upper) = if index < 7 { ('F', 'B') } else { ('L', 'R') };
+
+            if c != lower && c != upper {
+                return Err(ParseError::new(
+                    s,
+                    index..index + c.len_utf8(),
+                    format!("Expected '{}' or '{}'", lower, upper),
+                ));
+            }
+        }
+
+        match s.len() {
+            10 => {}
+            len if len < 10 => {
+                return Err(ParseError::end(
+                    s,
+                    "Expected 10 characters, e.g. 'FBFBBFFRLR'",
+                ))
+            }
+            len => {
+                return Err(ParseError::new(
+                    s,
+                    10..len,
+                    "Unexpected characters after the seat",
+                ))
+            }
+        }
+
+        let chars = s.chars();
+
+        let row_sequence = chars.clone().take(7);
+        let column_sequence = chars.skip(7).take(3);
+
+        let row_id = collapse_range(0, 127, 'B', 'F', row_sequence);
+        let column_id = collapse_range(0, 7, 'R', 'L', column_sequence);
+
+        Ok(SeatId { row_id, column_id })
+    }
+}
+
+fn collapse_range<T: Iterator<Item = char>>(
+    min: i32,
+    max: i32,
+    sel_upper: char,
+    sel_lower: char,
+    sequence: T,
+) -> i32 {
+    sequence
+        .fold(
+            Range {
+                start: min,
+                end: max,
+            },
+            |range, operator| match operator {
+                c if c == sel_lower => Range {
+                    start: range.start,
+                    end: (range.start + range.end) / 2,
+                },
+                c if c == sel_upper => Range {
+                    start: round_up(range.start, range.end),
+                    end: range.end,
+                },
+                _ => unreachable!("Unknown operator: {}", operator),
+            },
+        )
+        .start
+}
+
+fn round_up(first: i32, second: i32) -> i32 {
+    (((first as f64 + second as f64) / 2.0) + 0.5) as i32
+}
+
+/// Boarding passes for a run of consecutive seats with one missing from the middle.
+fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+    const SEATS: usize = 128 * 8;
+
+    let size = size.max(2);
+    if size > SEATS - 2 {
+        bail!(
+            "There are only {} seats, so at most {} passes",
+            SEATS,
+            SEATS - 2
+        );
+    }
+
+    let first = rng.range(0..SEATS - size);
+    let missing = rng.range(first + 1..first + size);
+    let mut seats = (first..=first + size)
+        .filter(|seat| *seat != missing)
+        .collect::<Vec<usize>>();
+    rng.shuffle(&mut seats);
+
+    // The row is the top seven bits of the seat ID and the column the bottom three.
+    Ok(lines(seats.iter().map(|seat| {
+        (0..10)
+            .map(|bit| match (bit < 7, (seat >> (9 - bit)) & 1 == 1) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            })
+            .collect::<String>()
+    })))
+}
+
+// The example only gives seat IDs, so there is no empty seat to find for part 2.
+examples! {
+    Day05;
+
+    boarding_passes {
+        input: "\
+            FBFBBFFRLR\n\
+            BFFFBBFRRR\n\
+            FFFBBBFRRR\n\
+            BBFFBBFRLL",
+        part1: 820,
+    }
+}
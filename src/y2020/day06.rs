@@ -0,0 +1,173 @@
+//! Day 6: Custom Customs. Counts the questions that groups answered "yes" to.
+
+use std::str::FromStr;
+
+use crate::{
+    generate::{lines, Rng},
+    input_records,
+    solution::Solution,
+    ParseError,
+};
+use anyhow::{Context, Result};
+
+/// The day 6 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 6;
+    type Input = Vec<Vec<Answers>>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse() -> Result<Self::Input> {
+        input_records(Self::YEAR, Self::DAY)?
+            .map(|record| {
+                let record = record.context("Failed to read record")?;
+
+                record
+                    .iter()
+                    .enumerate()
+                    .map(|(index, line)| {
+                        line.parse::<Answers>()
+                            .map_err(|e| e.with_line(record.line_number(index)).into())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Total sum: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Total sum: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(groups: &[Vec<Answers>]) -> u32 {
+    groups
+        .iter()
+        .map(|group| group.iter().fold(0, |mask, answers| mask | answers.mask))
+        .map(u32::count_ones)
+        .sum()
+}
+
+fn part2(groups: &[Vec<Answers>]) -> u32 {
+    groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .fold(u32::MAX, |mask, answers| mask & answers.mask)
+        })
+        .map(u32::count_ones)
+        .sum()
+}
+
+/// The questions one person answered "yes" to, as a bit per letter.
+pub struct Answers {
+    mask: u32,
+}
+
+impl Answers {
+    /// Whether `question`, a letter from 'a' to 'z', was answered "yes".
+    pub fn contains(&self, question: char) -> bool {
+        question.is_ascii_lowercase() && self.mask & (1 << (question as u32 - 'a' as u32)) != 0
+    }
+
+    /// How many questions were answered "yes".
+    pub fn count(&self) -> u32 {
+        self.mask.count_ones()
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mask = 0;
+
+        for (index, c) in s.char_indices() {
+            if !c.is_ascii_lowercase() {
+                return Err(ParseError::new(
+                    s,
+                    index..index + c.len_utf8(),
+                    format!("Expected a question from 'a' to 'z', found '{}'", c),
+                ));
+            }
+
+            mask |= 1 << (c as u32 - 'a' as u32);
+        }
+
+        Ok(Answers { mask })
+    }
+}
+
+/// Groups of one to five people, who share a few answers and each add some of their own.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    fn add_letters(rng: &mut Rng, answers: &mut String, count: usize) {
+        for _ in 0..count {
+            let letter = rng.letter();
+            if !answers.contains(letter) {
+                answers.push(letter);
+            }
+        }
+    }
+
+    let groups = (0..size).map(|_| {
+        let mut shared = String::new();
+        let count = rng.range(0..4);
+        add_letters(rng, &mut shared, count);
+
+        (0..rng.range(1..6))
+            .map(|_| {
+                let mut answers = shared.clone();
+                let count = rng.range(1..6);
+                add_letters(rng, &mut answers, count);
+                answers
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    });
+
+    lines([groups.collect::<Vec<_>>().join("\n\n")])
+}
+
+examples! {
+    Day06;
+
+    groups {
+        input: "\
+            abc\n\
+            \n\
+            a\n\
+            b\n\
+            c\n\
+            \n\
+            ab\n\
+            ac\n\
+            \n\
+            a\n\
+            a\n\
+            a\n\
+            a\n\
+            \n\
+            b",
+        part1: 11,
+        part2: 6,
+    }
+}
@@ -0,0 +1,304 @@
+//! Day 7: Handy Haversacks. Follows the rules for which bags hold which other bags.
+
+use std::{collections::HashMap, collections::HashSet, str::FromStr};
+
+use crate::{
+    generate::{lines, Rng},
+    input_parsed, parse_field,
+    solution::Solution,
+    ParseError,
+};
+use anyhow::Result;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// The day 7 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day07;
+
+impl Solution for Day07 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 7;
+    type Input = Vec<Relationship>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse() -> Result<Self::Input> {
+        input_parsed(Self::YEAR, Self::DAY)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Found {} possible parents for 'shiny gold'", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("A shiny gold bag contains {} bags", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(input: &[Relationship]) -> usize {
+    let mut graph = HashMap::new();
+
+    for value in input {
+        for (_, parent) in &value.contains {
+            let entry = graph.entry(parent.clone()).or_insert(HashSet::new());
+            entry.insert(value.ident.clone());
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut queue = Vec::new();
+
+    queue.extend(graph.get("shiny gold").unwrap().clone());
+
+    while let Some(item) = queue.pop() {
+        if visited.contains(&item) {
+            continue;
+        }
+
+        visited.insert(item.clone());
+
+        if graph.contains_key(&item) {
+            queue.extend(graph.get(&item).unwrap().clone());
+        }
+    }
+
+    visited.len()
+}
+
+fn part2(input: &[Relationship]) -> usize {
+    let graph = input
+        .iter()
+        .map(|rela| (rela.ident.clone(), rela.clone()))
+        .collect::<HashMap<String, Relationship>>();
+
+    fn child_bag_count(
+        graph: &HashMap<String, Relationship>,
+        ident: &String,
+        parent_count: usize,
+    ) -> usize {
+        match graph.get(ident) {
+            Some(relationship) => relationship
+                .contains
+                .iter()
+                .fold(0, |total, (count, child)| {
+                    total
+                        + parent_count * count
+                        + child_bag_count(graph, child, parent_count * count)
+                }),
+            None => 0,
+        }
+    }
+
+    child_bag_count(&graph, &"shiny gold".into(), 1)
+}
+
+/// One rule, like `light red bags contain 1 bright white bag, 2 muted yellow bags.`
+#[derive(Clone)]
+pub struct Relationship {
+    /// The colour of the outer bag.
+    pub ident: String,
+    /// How many of each colour of bag it must directly contain.
+    pub contains: Vec<(usize, String)>,
+}
+
+impl FromStr for Relationship {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (ident, contains_parts) = s
+            .split_once(" bags contain ")
+            .ok_or_else(|| ParseError::whole(s, "Expected '<colour> bags contain ...'"))?;
+
+        lazy_static! {
+            static ref RE: Regex = Regex::new(r"([0-9]{1,}) ([a-zA-Z\s]*) bags?[,|.]").unwrap();
+        }
+
+        let contains = RE
+            .captures_iter(contains_parts)
+            .map(|cap| Ok((parse_field(s, &cap[1], "count")?, cap[2].into())))
+            .collect::<Result<Vec<(usize, String)>, ParseError>>()?;
+
+        if contains.is_empty() && contains_parts != "no other bags." {
+            return Err(ParseError::at(
+                s,
+                contains_parts,
+                "Expected a list of bags or 'no other bags.'",
+            ));
+        }
+
+        Ok(Relationship {
+            ident: ident.into(),
+            contains,
+        })
+    }
+}
+
+/// Bag rules forming a DAG around "shiny gold". Every bag is given a layer and only contains
+/// bags from deeper layers, which keeps the nesting, and so part 2's count, bounded.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const LAYERS: usize = 7;
+    const ADJECTIVES: &[&str] = &[
+        "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+        "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+    ];
+    const COLOURS: &[&str] = &[
+        "aqua",
+        "beige",
+        "black",
+        "blue",
+        "bronze",
+        "brown",
+        "chartreuse",
+        "coral",
+        "crimson",
+        "cyan",
+        "fuchsia",
+        "gold",
+        "gray",
+        "green",
+        "indigo",
+        "lavender",
+        "lime",
+        "magenta",
+        "maroon",
+        "olive",
+        "orange",
+        "plum",
+        "purple",
+        "red",
+        "salmon",
+        "silver",
+        "tan",
+        "teal",
+        "tomato",
+        "turquoise",
+        "violet",
+        "white",
+        "yellow",
+    ];
+
+    let size = size.max(3);
+
+    // Once the real names start running out, make up adjectives.
+    let mut names = vec!["shiny gold".to_owned()];
+    let mut seen = names.iter().cloned().collect::<HashSet<String>>();
+    while names.len() < size {
+        let adjective = if seen.len() < ADJECTIVES.len() * COLOURS.len() / 2 {
+            rng.pick(ADJECTIVES).to_string()
+        } else {
+            (0..rng.range(2..4))
+                .map(|_| {
+                    format!(
+                        "{}{}",
+                        rng.pick(&['b', 'd', 'f', 'g', 'l', 'm', 'n', 'p', 'r', 's', 't', 'v']),
+                        rng.pick(&['a', 'e', 'i', 'o', 'u'])
+                    )
+                })
+                .collect()
+        };
+
+        let name = format!("{} {}", adjective, rng.pick(COLOURS));
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    // Shiny gold sits in layer 2, with at least one bag above it and one below.
+    let mut layers = vec![2, 0, 3];
+    layers.extend((3..size).map(|_| rng.range(0..LAYERS)));
+
+    let mut by_layer = (0..size).collect::<Vec<usize>>();
+    rng.shuffle(&mut by_layer);
+    by_layer.sort_by_key(|bag| layers[*bag]);
+
+    let mut contents = (0..size)
+        .map(|bag| {
+            let deeper =
+                &by_layer[by_layer.partition_point(|other| layers[*other] <= layers[bag])..];
+            if deeper.is_empty() || rng.chance(0.2) {
+                return Vec::new();
+            }
+
+            let mut children = (0..rng.range(1..5))
+                .map(|_| *rng.pick(deeper))
+                .collect::<Vec<usize>>();
+            children.sort_unstable();
+            children.dedup();
+
+            children
+                .into_iter()
+                .map(|child| (rng.range(1..6), child))
+                .collect::<Vec<(usize, usize)>>()
+        })
+        .collect::<Vec<_>>();
+
+    for (bag, child) in [(1, 0), (0, 2)] {
+        if !contents[bag].iter().any(|(_, other)| *other == child) {
+            contents[bag].push((rng.range(1..6), child));
+        }
+    }
+
+    let mut rules = (0..size)
+        .map(|bag| {
+            let list = contents[bag]
+                .iter()
+                .map(|(count, child)| {
+                    let plural = if *count == 1 { "" } else { "s" };
+                    format!("{} {} bag{}", count, names[*child], plural)
+                })
+                .collect::<Vec<String>>();
+
+            if list.is_empty() {
+                format!("{} bags contain no other bags.", names[bag])
+            } else {
+                format!("{} bags contain {}.", names[bag], list.join(", "))
+            }
+        })
+        .collect::<Vec<String>>();
+
+    rng.shuffle(&mut rules);
+    lines(rules)
+}
+
+examples! {
+    Day07;
+
+    bag_rules {
+        input: "\
+            light red bags contain 1 bright white bag, 2 muted yellow bags.\n\
+            dark orange bags contain 3 bright white bags, 4 muted yellow bags.\n\
+            bright white bags contain 1 shiny gold bag.\n\
+            muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.\n\
+            shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.\n\
+            dark olive bags contain 3 faded blue bags, 4 dotted black bags.\n\
+            vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.\n\
+            faded blue bags contain no other bags.\n\
+            dotted black bags contain no other bags.",
+        part1: 4,
+        part2: 32,
+    }
+
+    nested_bags {
+        input: "\
+            shiny gold bags contain 2 dark red bags.\n\
+            dark red bags contain 2 dark orange bags.\n\
+            dark orange bags contain 2 dark yellow bags.\n\
+            dark yellow bags contain 2 dark green bags.\n\
+            dark green bags contain 2 dark blue bags.\n\
+            dark blue bags contain 2 dark violet bags.\n\
+            dark violet bags contain no other bags.",
+        part2: 126,
+    }
+}
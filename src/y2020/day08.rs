@@ -0,0 +1,262 @@
+//! Day 8: Handheld Halting. Runs and repairs a small boot program.
+
+use std::{collections::HashSet, str::FromStr};
+
+use crate::{
+    budget,
+    generate::{lines, Rng},
+    input_parsed, parse_field,
+    solution::Solution,
+    ParseError,
+};
+use anyhow::{bail, Result};
+
+/// The day 8 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 8;
+    type Input = Vec<OpCode>;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse() -> Result<Self::Input> {
+        input_parsed(Self::YEAR, Self::DAY)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        part2(input)
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Final acc value: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!(
+            "Found permutation that finishes execution. Final acc value: {}",
+            answer
+        )
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(input: &[OpCode]) -> isize {
+    run_machine(input).0
+}
+
+fn part2(input: &[OpCode]) -> Result<isize> {
+    let mut meter = budget::meter();
+    let mut i = 0;
+    loop {
+        meter.tick()?;
+        let mut cloned = input.to_vec();
+
+        while let Some(OpCode::Acc(_)) = cloned.get(i) {
+            i += 1;
+        }
+
+        if i == cloned.len() {
+            bail!("No single jmp/nop change lets the program finish");
+        }
+
+        // Change the next jmp/nop execution.
+        cloned[i] = match cloned[i].clone() {
+            OpCode::Nop(val) => OpCode::Jmp(val),
+            OpCode::Acc(val) => OpCode::Acc(val),
+            OpCode::Jmp(val) => OpCode::Nop(val),
+        };
+
+        i += 1;
+
+        let (acc_result, end_cursor) = run_machine(&cloned);
+
+        if end_cursor == cloned.len() {
+            return Ok(acc_result);
+        }
+    }
+}
+
+/// Runs `input` from the first instruction until it's about to repeat one or has jumped outside
+/// the program. Returns the accumulator and the cursor it stopped at, which is `input.len()` if
+/// the program ran off its end.
+///
+/// ```
+/// use aoc_2020::y2020::day08::{run_machine, OpCode};
+///
+/// let program = ["nop +0", "acc +1", "jmp -2"]
+///     .iter()
+///     .map(|line| line.parse())
+///     .collect::<Result<Vec<OpCode>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(run_machine(&program), (1, 0));
+/// ```
+pub fn run_machine(input: &[OpCode]) -> (isize, usize) {
+    let mut visited = HashSet::new();
+    let mut cursor: usize = 0;
+    let mut acc: isize = 0;
+
+    while !visited.contains(&cursor) && cursor < input.len() {
+        visited.insert(cursor);
+        match input[cursor] {
+            OpCode::Nop(_) => cursor += 1,
+            OpCode::Acc(value) => {
+                acc += value;
+                cursor += 1;
+            }
+            OpCode::Jmp(value) => cursor = cursor.wrapping_add(value as usize),
+        }
+    }
+
+    (acc, cursor)
+}
+
+/// A boot code instruction, like `acc +3`.
+#[derive(Clone, Debug)]
+pub enum OpCode {
+    /// Does nothing and moves to the next instruction.
+    Nop(isize),
+    /// Adds to the accumulator and moves to the next instruction.
+    Acc(isize),
+    /// Moves the cursor by the offset.
+    Jmp(isize),
+}
+
+impl FromStr for OpCode {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (op_code, value) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::end(s, "Expected an argument, e.g. 'nop +0'"))?;
+        let value = parse_field(s, value, "argument")?;
+
+        let op_code = match op_code {
+            "nop" => OpCode::Nop(value),
+            "acc" => OpCode::Acc(value),
+            "jmp" => OpCode::Jmp(value),
+            _ => {
+                return Err(ParseError::at(
+                    s,
+                    op_code,
+                    format!("Unknown op code '{}'", op_code),
+                ))
+            }
+        };
+
+        Ok(op_code)
+    }
+}
+
+/// A boot program that loops, where exactly one `jmp` has to become a `nop` for it to run off the
+/// end. The program is made of two-instruction slots, an `acc` followed by a jump or another
+/// `acc`. The loop and the path from the broken `jmp` to the end wind through random slots, and
+/// everything else jumps back into the loop so that no other change escapes it.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    fn acc(rng: &mut Rng) -> String {
+        format!("acc {:+}", rng.range(0..100) as isize - 50)
+    }
+
+    /// Jumps from the second instruction of slot `from` to the start of slot `to`.
+    fn jump(op: &str, from: usize, to: usize) -> String {
+        format!("{} {:+}", op, 2 * to as isize - (2 * from + 1) as isize)
+    }
+
+    let slots = (size / 2).max(8);
+    let broken = rng.range(1..slots - 1);
+
+    let mut free = (1..slots)
+        .filter(|i| *i != broken && *i != broken + 1)
+        .collect::<Vec<usize>>();
+    rng.shuffle(&mut free);
+
+    // The fixed program leaves the loop for this path. Slot 1 is kept off it so that the first
+    // jump can't be a second way out.
+    let mut exit = vec![broken + 1];
+    exit.extend(free.iter().copied().filter(|i| *i != 1).take(slots / 4));
+
+    let mut on_exit = vec![false; slots + 1];
+    for i in &exit {
+        on_exit[*i] = true;
+    }
+
+    // Nothing in the loop may sit just before the exit path or the end of the program, or
+    // turning its `jmp` into a `nop` would escape as well.
+    let mut cycle = free
+        .iter()
+        .copied()
+        .filter(|i| !on_exit[*i] && !on_exit[i + 1] && i + 1 < slots)
+        .take(slots / 4)
+        .collect::<Vec<usize>>();
+    cycle.insert(rng.range(0..cycle.len() + 1), broken);
+    cycle.insert(0, 0);
+
+    let mut program = vec![None; slots];
+
+    for (index, &i) in cycle.iter().enumerate() {
+        let next = match cycle.get(index + 1) {
+            Some(next) => *next,
+            None => cycle[rng.range(0..index)],
+        };
+
+        program[i] = Some(if next != i + 1 {
+            jump("jmp", i, next)
+        } else if rng.chance(0.5) {
+            acc(rng)
+        } else {
+            jump("nop", i, *rng.pick(&cycle))
+        });
+    }
+
+    for (index, &i) in exit.iter().enumerate() {
+        let next = exit.get(index + 1).copied().unwrap_or(slots);
+
+        program[i] = Some(if next != i + 1 {
+            jump("jmp", i, next)
+        } else {
+            acc(rng)
+        });
+    }
+
+    // The remaining slots are never reached, so they only need to look like the rest.
+    for (i, instruction) in program.iter_mut().enumerate() {
+        if instruction.is_none() {
+            *instruction = Some(jump("jmp", i, *rng.pick(&cycle)));
+        }
+    }
+
+    lines(
+        program
+            .into_iter()
+            .flatten()
+            .flat_map(|instruction| [acc(rng), instruction]),
+    )
+}
+
+examples! {
+    Day08;
+
+    boot_code {
+        input: "\
+            nop +0\n\
+            acc +1\n\
+            jmp +4\n\
+            acc +3\n\
+            jmp -3\n\
+            acc -99\n\
+            acc +1\n\
+            jmp -4\n\
+            acc +6",
+        part1: 5,
+        part2: 8,
+    }
+}
@@ -0,0 +1,210 @@
+//! Day 9: Encoding Error. Finds the number that breaks the XMAS cipher's sum rule.
+
+use crate::{
+    budget,
+    generate::{lines, Rng},
+    input_parsed,
+    solution::Solution,
+};
+use anyhow::{Context, Result};
+use itertools::Itertools;
+
+/// How many numbers each number may be the sum of two of in the puzzle input.
+const PREAMBLE: usize = 25;
+
+/// The day 9 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 9;
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse() -> Result<Self::Input> {
+        input_parsed(Self::YEAR, Self::DAY)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        find_invalid_number(input, PREAMBLE).context("Every number has the sum property")
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        part2(input, PREAMBLE)
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("First number without the sum property: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Encryption weakness is: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        generate(rng, size)
+    }
+}
+
+/// The first number after the preamble that isn't the sum of two different numbers among the
+/// `preamble` before it. The puzzle input uses a preamble of 25.
+pub fn find_invalid_number(input: &[usize], preamble: usize) -> Option<usize> {
+    let mut possible_numbers = input.iter().cloned().take(preamble).collect::<Vec<usize>>();
+
+    fn is_sum_of(possible: &[usize], target: usize) -> bool {
+        for x in possible.iter() {
+            for y in possible.iter() {
+                if x == y {
+                    continue;
+                }
+
+                if *x + *y == target {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    for number in input.iter().skip(preamble) {
+        if is_sum_of(&possible_numbers, *number) {
+            possible_numbers.remove(0);
+            possible_numbers.push(*number);
+        } else {
+            return Some(*number);
+        }
+    }
+
+    None
+}
+
+fn part2(input: &[usize], preamble: usize) -> Result<usize> {
+    let target =
+        find_invalid_number(input, preamble).context("Every number has the sum property")?;
+
+    // The run has to be at least two numbers long, so the invalid number can't be its own run.
+    const NO_RUN: &str = "No contiguous run of at least two numbers sums to the invalid number";
+
+    let mut meter = budget::meter();
+    let mut cursor = 0;
+    let mut size = 2;
+
+    loop {
+        meter.tick()?;
+        let mut sum = input
+            .get(cursor..cursor + size)
+            .context(NO_RUN)?
+            .iter()
+            .sum::<usize>();
+
+        while sum < target {
+            sum += input.get(cursor + size).context(NO_RUN)?;
+            size += 1;
+        }
+
+        if sum == target {
+            break;
+        } else {
+            cursor += 1;
+            size = 2;
+        }
+    }
+
+    let range = &input[cursor..cursor + size];
+    let (min, max) = range.iter().minmax().into_option().context(NO_RUN)?;
+
+    Ok(min + max)
+}
+
+/// Numbers that are each the sum of two of the `PREAMBLE` before them, except for one which is
+/// the sum of an earlier contiguous run instead. Each sum uses some of the smallest numbers in
+/// its window, but the numbers still double every few dozen lines, so they can't go on for long.
+/// The invalid number comes within the first `CHAIN` lines, and after it the numbers start again
+/// from a fresh preamble every `CHAIN` lines. Neither part looks past the first invalid number.
+fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+    const CHAIN: usize = 1000;
+
+    fn preamble(rng: &mut Rng) -> Vec<usize> {
+        let mut numbers = (1..=PREAMBLE * 2).collect::<Vec<usize>>();
+        rng.shuffle(&mut numbers);
+        numbers.truncate(PREAMBLE);
+        numbers
+    }
+
+    let size = size.max(PREAMBLE + 2);
+    let mut numbers = preamble(rng);
+
+    let earliest = (size * 3 / 4).clamp(PREAMBLE + 1, CHAIN - 1);
+    let invalid_at = rng.range(earliest..size.min(CHAIN));
+
+    while numbers.len() < size {
+        if numbers.len().is_multiple_of(CHAIN) {
+            numbers.extend(preamble(rng));
+            continue;
+        }
+
+        let mut window = numbers[numbers.len() - PREAMBLE..].to_vec();
+        window.sort_unstable();
+        window.dedup();
+
+        let is_sum_of = |target: usize| {
+            window.iter().any(|x| {
+                window
+                    .iter()
+                    .any(|y| x != y && x.checked_add(*y) == Some(target))
+            })
+        };
+
+        let next = if numbers.len() == invalid_at {
+            loop {
+                let start = rng.range(0..numbers.len() - 1);
+                let end = rng.range(start + 2..(start + 17).min(numbers.len()) + 1);
+                let sum = numbers[start..end]
+                    .iter()
+                    .try_fold(0usize, |sum, n| sum.checked_add(*n));
+
+                match sum {
+                    Some(sum) if !is_sum_of(sum) => break sum,
+                    _ => continue,
+                }
+            }
+        } else {
+            let first = rng.range(0..3);
+            let second = (first + rng.range(1..3)) % 3;
+
+            window[first] + window[second]
+        };
+
+        numbers.push(next);
+    }
+    numbers.truncate(size);
+
+    Ok(lines(numbers.iter().map(|number| number.to_string())))
+}
+
+// The puzzle's example uses a preamble of 5 numbers rather than 25, so it calls the parts directly
+// instead of going through `examples!`.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [usize; 20] = [
+        35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576,
+    ];
+
+    #[test]
+    fn example() {
+        assert_eq!(find_invalid_number(&EXAMPLE, 5), Some(127));
+        assert_eq!(part2(&EXAMPLE, 5).unwrap(), 62);
+    }
+
+    /// An invalid number of 0 used to match the empty run past the end of the input.
+    #[test]
+    fn needs_two_numbers() {
+        assert_eq!(find_invalid_number(&[1, 2, 0, 3], 2), Some(0));
+        assert!(part2(&[1, 2, 0, 3], 2).is_err());
+        assert!(part2(&[1, 2, 4, 3], 2).is_err());
+    }
+}
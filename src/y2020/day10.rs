@@ -0,0 +1,191 @@
+//! Day 10: Adapter Array. Chains joltage adapters and counts their arrangements.
+
+use std::collections::HashMap;
+
+use crate::{
+    generate::{lines, Rng},
+    input_parsed,
+    solution::Solution,
+};
+use anyhow::Result;
+
+/// The day 10 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 10;
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse() -> Result<Self::Input> {
+        input_parsed(Self::YEAR, Self::DAY)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(input.clone()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2(input.clone()))
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Result: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Total permutations: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(mut input: Vec<usize>) -> usize {
+    // The starting point.
+    input.push(0);
+    input.sort();
+
+    // There's always a difference of 3 between the last adapter and my device.
+    let mut results = [0, 0, 0, 1];
+
+    for pair in input.windows(2) {
+        let diff = pair[1] - pair[0];
+        results[diff] += 1;
+    }
+
+    results[1] * results[3]
+}
+
+fn part2(mut input: Vec<usize>) -> u64 {
+    fn calculate_permutations(
+        current_value: usize,
+        remaining: &[usize],
+        cache: &mut HashMap<usize, u64>,
+    ) -> u64 {
+        // The base case is that we are at '0', there is only 1 way to get to '0'.
+        if remaining.is_empty() {
+            return 1;
+        }
+
+        // If we've already checked how many ways to get to current_value, return it in the cache.
+        if let Some(val) = cache.get(&current_value) {
+            return *val;
+        }
+
+        // Otherwise sum the various ways of getting to current_value by traversing all
+        // possible directions recursively.
+        let value = remaining
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| current_value - *value <= 3)
+            .fold(0u64, |total, (index, value)| {
+                total + calculate_permutations(*value, &remaining[index + 1..], cache)
+            });
+
+        // Save into the cache!
+        cache.insert(current_value, value);
+        value
+    }
+
+    // Insert the starting point and sort the input in largest --> smallest
+    input.push(0);
+    input.sort();
+    input.reverse();
+
+    let mut cache: HashMap<usize, u64> = HashMap::new();
+    calculate_permutations(input[0] + 3, &input, &mut cache)
+}
+
+/// Adapters in runs one jolt apart, separated by gaps of three. Runs are kept short enough that
+/// part 2's count of arrangements fits in 64 bits.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    // The ways to arrange a run of `n` one-jolt steps, as powers of two.
+    let arrangements = [1.0f64, 1.0, 2.0, 4.0, 7.0].map(f64::log2);
+    let mut budget = 60.0;
+
+    let mut adapters = Vec::new();
+    let mut joltage = 0;
+
+    while adapters.len() < size.max(1) {
+        let mut steps = rng.range(0..arrangements.len());
+        if arrangements[steps] > budget {
+            steps = 1;
+        }
+        budget -= arrangements[steps];
+
+        for _ in 0..steps {
+            joltage += 1;
+            adapters.push(joltage);
+        }
+
+        joltage += 3;
+        adapters.push(joltage);
+    }
+
+    adapters.truncate(size.max(1));
+    rng.shuffle(&mut adapters);
+
+    lines(adapters.iter().map(|adapter| adapter.to_string()))
+}
+
+examples! {
+    Day10;
+
+    small {
+        input: "\
+            16\n\
+            10\n\
+            15\n\
+            5\n\
+            1\n\
+            11\n\
+            7\n\
+            19\n\
+            6\n\
+            12\n\
+            4",
+        part1: 35,
+        part2: 8,
+    }
+
+    large {
+        input: "\
+            28\n\
+            33\n\
+            18\n\
+            42\n\
+            31\n\
+            14\n\
+            46\n\
+            20\n\
+            48\n\
+            47\n\
+            24\n\
+            23\n\
+            49\n\
+            45\n\
+            19\n\
+            38\n\
+            39\n\
+            11\n\
+            1\n\
+            32\n\
+            25\n\
+            35\n\
+            8\n\
+            17\n\
+            7\n\
+            9\n\
+            4\n\
+            2\n\
+            34\n\
+            10\n\
+            3",
+        part1: 220,
+        part2: 19208,
+    }
+}
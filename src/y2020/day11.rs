@@ -0,0 +1,278 @@
+//! Day 11: Seating System. Simulates people filling a seating area until it settles.
+
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+};
+
+use crate::{
+    budget,
+    generate::Rng,
+    grid::{Grid, ADJACENT},
+    input_grid,
+    solution::Solution,
+};
+use anyhow::Result;
+
+/// The day 11 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 11;
+    type Input = SeatingArrangement;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse() -> Result<Self::Input> {
+        let data = input_grid(Self::YEAR, Self::DAY, |c| match c {
+            'L' => Some(ElementState::Free),
+            '.' => Some(ElementState::Floor),
+            '#' => Some(ElementState::Occupied),
+            _ => None,
+        })?;
+
+        Ok(SeatingArrangement { data })
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        SeatingArrangement::run::<Part1>(input.clone())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        SeatingArrangement::run::<Part2>(input.clone())
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Part 1 occupied seats: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Part 2 occuped seats: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        generate(rng, size)
+    }
+}
+
+/// The first part's rules: people look at the eight seats around them, and leave if four or more
+/// are taken.
+pub struct Part1;
+
+impl SeatCalculateStrategy for Part1 {
+    const OCCUPIED_THRESHOLD: u32 = 4;
+
+    fn count_occupied_seats(arr: &SeatingArrangement, x: usize, y: usize) -> u32 {
+        arr.data
+            .neighbours8(x, y)
+            .filter(|(_, element)| **element == ElementState::Occupied)
+            .count() as u32
+    }
+}
+
+/// The second part's rules: people look past the floor to the first seat in each of the eight
+/// directions, and leave if five or more are taken.
+pub struct Part2;
+
+impl SeatCalculateStrategy for Part2 {
+    const OCCUPIED_THRESHOLD: u32 = 5;
+
+    fn count_occupied_seats(arr: &SeatingArrangement, x: usize, y: usize) -> u32 {
+        // Look past the floor in each direction to the first visible seat.
+        ADJACENT
+            .iter()
+            .filter_map(|direction| {
+                arr.data
+                    .ray(x, y, *direction)
+                    .map(|(_, element)| *element)
+                    .find(|element| *element != ElementState::Floor)
+            })
+            .filter(|element| *element == ElementState::Occupied)
+            .count() as u32
+    }
+}
+
+/// A position in the seating area: `.`, `L` or `#`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ElementState {
+    Floor,
+    Free,
+    Occupied,
+}
+
+impl Display for ElementState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ElementState::Floor => ".",
+            ElementState::Free => "L",
+            ElementState::Occupied => "#",
+        })
+    }
+}
+
+/// The state of the whole seating area.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SeatingArrangement {
+    pub data: Grid<ElementState>,
+}
+
+impl SeatingArrangement {
+    /// Applies `T`'s rules until nobody moves, and returns how many seats are taken.
+    ///
+    /// An arrangement that doesn't settle runs until it's out of budget, which `unsettled` can
+    /// check for up front.
+    pub fn run<T: SeatCalculateStrategy>(mut input: SeatingArrangement) -> Result<usize> {
+        let mut meter = budget::meter();
+
+        loop {
+            let next = input.step::<T>();
+
+            if next == input {
+                break;
+            }
+
+            meter.tick()?;
+            input = next;
+        }
+
+        Ok(input.num_occupied())
+    }
+
+    /// The seats that never settle under `T`'s rules, which is empty if the arrangement does
+    /// settle. Arrangements that don't end up alternating between two states instead.
+    pub fn unsettled<T: SeatCalculateStrategy>(&self) -> Vec<(usize, usize)> {
+        let mut previous = self.clone();
+        let mut current = self.step::<T>();
+
+        loop {
+            let next = current.step::<T>();
+
+            if next == current {
+                return Vec::new();
+            } else if next == previous {
+                return next
+                    .data
+                    .iter()
+                    .filter(|(position, element)| current.data[*position] != **element)
+                    .map(|(position, _)| position)
+                    .collect();
+            }
+
+            previous = current;
+            current = next;
+        }
+    }
+
+    fn num_occupied(&self) -> usize {
+        self.data
+            .iter()
+            .filter(|(_, element)| **element == ElementState::Occupied)
+            .count()
+    }
+
+    fn step<T: SeatCalculateStrategy>(&self) -> SeatingArrangement {
+        SeatingArrangement {
+            data: self.data.map(|(x, y), element| match *element {
+                ElementState::Floor => ElementState::Floor,
+                ElementState::Free => {
+                    if T::count_occupied_seats(self, x, y) == 0 {
+                        ElementState::Occupied
+                    } else {
+                        ElementState::Free
+                    }
+                }
+                ElementState::Occupied => {
+                    if T::count_occupied_seats(self, x, y) >= T::OCCUPIED_THRESHOLD {
+                        ElementState::Free
+                    } else {
+                        ElementState::Occupied
+                    }
+                }
+            }),
+        }
+    }
+}
+
+/// The rules people follow when deciding whether to sit down or leave.
+pub trait SeatCalculateStrategy {
+    /// How many of the seats counted by `count_occupied_seats` have to be taken for someone to
+    /// leave.
+    const OCCUPIED_THRESHOLD: u32;
+
+    /// How many of the seats that the person at `(x, y)` pays attention to are taken. People
+    /// only sit in a seat when this is 0.
+    fn count_occupied_seats(arr: &SeatingArrangement, x: usize, y: usize) -> u32;
+}
+
+/// An empty seating area 90 seats wide, like the real input, and `size` rows long.
+///
+/// Under the second part's rules, most people in a random layout sit down and stand up together,
+/// and only stop once the seats settled at the top and bottom edges reach them, which takes as
+/// many steps as there are rows. So every 30 rows there is a row with just one seat on the left
+/// and another with just one seat on the right. Nobody ever leaves those seats, which lets the
+/// rows around them settle in a few dozen steps however long the area is.
+///
+/// Random layouts don't always settle at all, so all the seats that keep changing are turned into
+/// floor at once, until both parts' rules settle.
+fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+    const WIDTH: usize = 90;
+    const BLOCK: usize = 32;
+
+    let mut arrangement = SeatingArrangement {
+        data: Grid::from_fn(WIDTH, size.max(1), |x, y| {
+            let seat = match y % BLOCK {
+                30 => x == 0,
+                31 => x == WIDTH - 1,
+                _ => rng.chance(0.65),
+            };
+
+            if seat {
+                ElementState::Free
+            } else {
+                ElementState::Floor
+            }
+        })?,
+    };
+
+    loop {
+        let mut seats = arrangement.unsettled::<Part1>();
+        if seats.is_empty() {
+            seats = arrangement.unsettled::<Part2>();
+        }
+        if seats.is_empty() {
+            break;
+        }
+
+        let seats = seats.into_iter().collect::<HashSet<_>>();
+        arrangement.data = arrangement.data.map(|position, element| {
+            if seats.contains(&position) {
+                ElementState::Floor
+            } else {
+                *element
+            }
+        });
+    }
+
+    Ok(format!("{}\n", arrangement.data))
+}
+
+examples! {
+    Day11;
+
+    seating {
+        input: "\
+            L.LL.LL.LL\n\
+            LLLLLLL.LL\n\
+            L.L.L..L..\n\
+            LLLL.LL.LL\n\
+            L.LL.LL.LL\n\
+            L.LLLLL.LL\n\
+            ..L.L.....\n\
+            LLLLLLLLLL\n\
+            L.LLLLLL.L\n\
+            L.LLLLL.LL",
+        part1: 37,
+        part2: 26,
+    }
+}
@@ -0,0 +1,297 @@
+//! Day 12: Rain Risk. Steers a ferry, directly and then by a waypoint.
+
+use std::str::FromStr;
+
+use crate::{
+    generate::{lines, Rng},
+    input_parsed, parse_field,
+    solution::Solution,
+    ParseError,
+};
+use anyhow::Result;
+
+/// The day 12 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 12;
+    type Input = Vec<Action>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse() -> Result<Self::Input> {
+        input_parsed(Self::YEAR, Self::DAY)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Final Manhattan distance: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Final Manhattan distance: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(inputs: &[Action]) -> i32 {
+    let mut ship = Ship::default();
+
+    for action in inputs {
+        ship.apply_p1(action);
+    }
+
+    ship.manhattan_distance()
+}
+
+fn part2(inputs: &[Action]) -> i32 {
+    let mut ship = Ship::default();
+    let mut waypoint = Position::new(10, 1);
+
+    for action in inputs {
+        ship.apply_p2(&mut waypoint, action);
+    }
+
+    ship.manhattan_distance()
+}
+
+/// The ferry, which starts at the origin facing east.
+pub struct Ship {
+    position: Position,
+    heading: Direction,
+}
+
+impl Default for Ship {
+    fn default() -> Self {
+        Ship {
+            position: Position::new(0, 0),
+            heading: Direction::East,
+        }
+    }
+}
+
+impl Ship {
+    pub fn position(&self) -> &Position {
+        &self.position
+    }
+
+    pub fn heading(&self) -> Direction {
+        self.heading
+    }
+
+    /// Follows `action` as the first part reads it, moving and turning the ship itself.
+    ///
+    /// Panics if a turn isn't a multiple of 90 degrees.
+    pub fn apply_p1(&mut self, action: &Action) {
+        match action {
+            Action::Move(dir, magnitude) => self.position.translate(*dir, *magnitude),
+            Action::Turn(dir, magnitude) => {
+                if magnitude % 90 != 0 {
+                    panic!("Found a turn that wasn't 90 degress!");
+                }
+
+                for _ in 0..(magnitude / 90) {
+                    self.heading = match dir {
+                        TurnDirection::Left => self.heading.rotate_ccw(),
+                        TurnDirection::Right => self.heading.rotate_cw(),
+                    }
+                }
+            }
+            Action::Forward(magnitude) => self.position.translate(self.heading, *magnitude),
+        }
+    }
+
+    /// Follows `action` as the second part reads it, where everything but `F` moves or turns the
+    /// `waypoint`, which is relative to the ship.
+    ///
+    /// Panics if a turn isn't a multiple of 90 degrees.
+    pub fn apply_p2(&mut self, waypoint: &mut Position, action: &Action) {
+        match action {
+            Action::Move(dir, magnitude) => waypoint.translate(*dir, *magnitude),
+            Action::Turn(dir, magnitude) => {
+                if magnitude % 90 != 0 {
+                    panic!("Found a turn that wasn't 90 degress!");
+                }
+
+                for _ in 0..(magnitude / 90) {
+                    waypoint.rotate(*dir)
+                }
+            }
+            Action::Forward(magnitude) => {
+                self.position.x += magnitude * waypoint.x;
+                self.position.y += magnitude * waypoint.y
+            }
+        }
+    }
+
+    /// How far the ship is from where it started.
+    pub fn manhattan_distance(&self) -> i32 {
+        self.position.manhattan_distance()
+    }
+}
+
+/// A navigation instruction, like `F10` or `R90`.
+pub enum Action {
+    /// `N`, `S`, `E` or `W`, and a distance.
+    Move(Direction, i32),
+    /// `L` or `R`, and a number of degrees.
+    Turn(TurnDirection, i32),
+    /// `F`, and a distance.
+    Forward(i32),
+}
+
+impl FromStr for Action {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let action = s
+            .chars()
+            .next()
+            .ok_or_else(|| ParseError::end(s, "Expected an action, e.g. 'F10'"))?;
+        let magnitude = &s[action.len_utf8()..];
+        let magnitude = parse_field(s, magnitude, "magnitude")?;
+
+        if (action == 'L' || action == 'R') && magnitude % 90 != 0 {
+            return Err(ParseError::new(
+                s,
+                1..s.len(),
+                "Turns must be a multiple of 90 degrees",
+            ));
+        }
+
+        Ok(match action {
+            'N' => Action::Move(Direction::North, magnitude),
+            'S' => Action::Move(Direction::South, magnitude),
+            'E' => Action::Move(Direction::East, magnitude),
+            'W' => Action::Move(Direction::West, magnitude),
+            'L' => Action::Turn(TurnDirection::Left, magnitude),
+            'R' => Action::Turn(TurnDirection::Right, magnitude),
+            'F' => Action::Forward(magnitude),
+            _ => {
+                return Err(ParseError::new(
+                    s,
+                    0..action.len_utf8(),
+                    format!("Unknown action '{}'", action),
+                ))
+            }
+        })
+    }
+}
+
+/// A compass direction. North is towards positive `y` and east towards positive `x`.
+#[derive(Debug, Copy, Clone)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    /// The direction 90 degrees clockwise.
+    pub fn rotate_cw(self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::South => Direction::West,
+            Direction::East => Direction::South,
+            Direction::West => Direction::North,
+        }
+    }
+
+    /// The direction 90 degrees anticlockwise.
+    pub fn rotate_ccw(self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+            Direction::West => Direction::South,
+        }
+    }
+}
+
+/// Which way an `L` or `R` action turns.
+#[derive(Debug, Copy, Clone)]
+pub enum TurnDirection {
+    Left,
+    Right,
+}
+
+/// A position or offset, with `x` east and `y` north.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Position {
+    pub fn new(x: i32, y: i32) -> Self {
+        Position { x, y }
+    }
+
+    /// Moves `magnitude` units in `direction`.
+    pub fn translate(&mut self, direction: Direction, magnitude: i32) {
+        match direction {
+            Direction::North => self.y += magnitude,
+            Direction::South => self.y -= magnitude,
+            Direction::East => self.x += magnitude,
+            Direction::West => self.x -= magnitude,
+        }
+    }
+
+    /// Rotates the offset 90 degrees about the origin.
+    pub fn rotate(&mut self, direction: TurnDirection) {
+        match direction {
+            TurnDirection::Left => {
+                // Transform (x,y) to (-y, x)
+                let tmp = self.y;
+                self.y = self.x;
+                self.x = -tmp;
+            }
+            TurnDirection::Right => {
+                // Transform (x,y) to (y, -x)
+                let tmp = self.x;
+                self.x = self.y;
+                self.y = -tmp;
+            }
+        }
+    }
+
+    pub fn manhattan_distance(&self) -> i32 {
+        self.x.abs() + self.y.abs()
+    }
+}
+
+/// Navigation instructions, with turns always a multiple of 90 degrees.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| match rng.range(0..7) {
+        0..=2 => format!("{}{}", rng.pick(&['N', 'S', 'E', 'W']), rng.range(1..6)),
+        3 => format!("{}{}", rng.pick(&['L', 'R']), rng.pick(&[90, 180, 270])),
+        _ => format!("F{}", rng.range(1..101)),
+    }))
+}
+
+examples! {
+    Day12;
+
+    navigation {
+        input: "\
+            F10\n\
+            N3\n\
+            F7\n\
+            R90\n\
+            F11",
+        part1: 25,
+        part2: 286,
+    }
+}
@@ -0,0 +1,244 @@
+//! Day 13: Shuttle Search. Finds the next bus, and the time when the buses line up.
+
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::{
+    budget,
+    generate::{lines, Rng},
+    input_lines, parse_field,
+    solution::Solution,
+    ParseError,
+};
+use anyhow::{bail, Context, Result};
+use log::trace;
+
+/// The day 13 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 13;
+    type Input = (usize, BusTimetable);
+    type Answer1 = Departure;
+    type Answer2 = usize;
+
+    fn parse() -> Result<Self::Input> {
+        let mut lines = input_lines(Self::YEAR, Self::DAY)?;
+
+        let earliest = lines
+            .next()
+            .context("Missing the earliest departure time")??;
+        let earliest =
+            parse_field(&earliest, &earliest, "departure time").map_err(|e| e.with_line(1))?;
+
+        let timetable = lines.next().context("Missing the bus timetable")??;
+        let timetable = BusTimetable::from_str(&timetable).map_err(|e| e.with_line(2))?;
+
+        Ok((earliest, timetable))
+    }
+
+    fn part1((earliest, timetable): &Self::Input) -> Result<Self::Answer1> {
+        part1(*earliest, timetable.clone()).context("The timetable has no buses")
+    }
+
+    fn part2((_, timetable): &Self::Input) -> Result<Self::Answer2> {
+        part2(timetable.clone())
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        if answer.wait == 0 {
+            format!("There is a bus exactly at {}. Result: 0.", answer.earliest)
+        } else {
+            format!(
+                "First bus you can catch is: {} and would need to wait {} minutes. Result = {}",
+                answer.id, answer.wait, answer
+            )
+        }
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Result: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+/// The first bus that can be caught. The answer is its ID multiplied by the wait.
+pub struct Departure {
+    earliest: usize,
+    id: usize,
+    wait: usize,
+}
+
+impl Departure {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// How many minutes after the earliest departure time the bus leaves.
+    pub fn wait(&self) -> usize {
+        self.wait
+    }
+}
+
+impl Display for Departure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id * self.wait)
+    }
+}
+
+fn part1(earliest: usize, timetable: BusTimetable) -> Option<Departure> {
+    if let Some((_, id)) = timetable
+        .ids
+        .iter()
+        .find(|(_, id)| earliest.is_multiple_of(*id))
+    {
+        return Some(Departure {
+            earliest,
+            id: *id,
+            wait: 0,
+        });
+    }
+
+    timetable
+        .ids
+        .iter()
+        .map(|(_, id)| (*id, *id - (earliest % id)))
+        .min_by_key(|(_, ttw)| *ttw)
+        .map(|(id, wait)| Departure { earliest, id, wait })
+}
+
+fn part2(timetable: BusTimetable) -> Result<usize> {
+    // We want to find 'N' such that for all bus 'b' with index 'i' the following is true:
+    //      (N + i) % b = 0
+    // This then simplifies to:
+    //      N % b = b - i since -i % b = b - i
+    // We can then apply CRT (https://en.wikipedia.org/wiki/Chinese_remainder_theorem) to solve the system of equations to find N.
+    let modulus: usize = timetable.ids.iter().map(|(_, id)| *id).product();
+
+    // Only exists when the bus IDs are coprime, which they are in the puzzle input.
+    fn inv_mod(a: usize, m: usize) -> Result<usize> {
+        // Everything is 0 mod 1, and coprime with it.
+        if m == 1 {
+            return Ok(0);
+        }
+
+        // First simplify...
+        let r = a % m;
+
+        // ...then brute force it.
+        let mut meter = budget::meter();
+        for candidate in 1..m {
+            meter.tick()?;
+
+            if (r * candidate) % m == 1 {
+                trace!("Inverse of {} mod {}: {}", a, m, candidate);
+                return Ok(candidate);
+            }
+        }
+
+        bail!(
+            "{} has no inverse mod {}, so the bus IDs aren't coprime",
+            a,
+            m
+        )
+    }
+
+    let x = timetable
+        .ids
+        .iter()
+        .map(|(index, id)| {
+            let ni = modulus / id;
+            let xi = inv_mod(ni, *id)?;
+
+            Ok(ni * xi * ((id - index % id) % id))
+        })
+        .sum::<Result<usize>>()?;
+
+    Ok(x % modulus)
+}
+
+/// The buses in service, parsed from a line like `7,13,x,x,59`.
+#[derive(Debug, Clone)]
+pub struct BusTimetable {
+    /// Each bus's position in the list and its ID, which is also how often it departs.
+    pub ids: Vec<(usize, usize)>,
+}
+
+impl FromStr for BusTimetable {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(BusTimetable {
+            ids: s
+                .split(",")
+                .enumerate()
+                .filter(|(_, fragment)| *fragment != "x")
+                .map(
+                    |(index, fragment)| match parse_field(s, fragment, "bus ID")? {
+                        0 => Err(ParseError::at(s, fragment, "Bus IDs must be at least 1")),
+                        id => Ok((index, id)),
+                    },
+                )
+                .collect::<Result<Vec<(usize, usize)>, ParseError>>()?,
+        })
+    }
+}
+
+/// A timetable of `size` slots holding distinct prime bus IDs, each at an offset smaller than
+/// itself. The product of the IDs is kept below 2^36 so that part 2 can't overflow.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let primes = (13..1000)
+        .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+        .collect::<Vec<usize>>();
+
+    let mut slots = vec![None; size];
+    slots[0] = Some(*rng.pick(&primes[..6]));
+    let mut product = slots[0].unwrap();
+
+    for _ in 0..size * 2 {
+        let id = *rng.pick(&primes);
+        let offset = rng.range(0..id.min(size));
+
+        if slots[offset].is_none() && !slots.contains(&Some(id)) && product * id < 1 << 36 {
+            slots[offset] = Some(id);
+            product *= id;
+        }
+    }
+
+    let timetable = slots
+        .iter()
+        .map(|slot| slot.map_or("x".to_owned(), |id| id.to_string()))
+        .collect::<Vec<String>>();
+
+    lines([
+        rng.range(100_000..1_000_000).to_string(),
+        timetable.join(","),
+    ])
+}
+
+examples! {
+    Day13;
+
+    timetable {
+        input: "\
+            939\n\
+            7,13,x,x,59,x,31,19",
+        part1: 295,
+        part2: 1068781,
+    }
+
+    every_minute {
+        input: "\
+            939\n\
+            1,x,7",
+        part1: 0,
+        part2: 5,
+    }
+}
@@ -0,0 +1,259 @@
+//! Day 14: Docking Data. Runs a docking program that writes through bitmasks.
+
+use crate::{
+    generate::{lines, Rng},
+    input_parsed, parse_field,
+    solution::Solution,
+    ParseError,
+};
+use anyhow::Result;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{collections::HashMap, str::FromStr};
+
+/// The day 14 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 14;
+    type Input = Vec<Instruction>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse() -> Result<Self::Input> {
+        input_parsed(Self::YEAR, Self::DAY)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Sum: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Sum: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(instructions: &[Instruction]) -> u64 {
+    let mut mem = HashMap::new();
+    let mut mask = Bitmask::default();
+
+    for inst in instructions {
+        match inst {
+            Instruction::SetMask(new_mask) => {
+                mask = new_mask.clone();
+            }
+            Instruction::Memset(addr, value) => {
+                mem.insert(addr, mask.apply(*value));
+            }
+        }
+    }
+
+    mem.values().copied().sum::<u64>()
+}
+
+fn part2(instructions: &[Instruction]) -> u64 {
+    let mut mem = HashMap::new();
+    let mut mask = Bitmask::default();
+
+    for inst in instructions {
+        match inst {
+            Instruction::SetMask(new_mask) => {
+                mask = new_mask.clone();
+            }
+            Instruction::Memset(addr, value) => {
+                for addr_permutation in mask.permute(*addr) {
+                    mem.insert(addr_permutation, value);
+                }
+            }
+        }
+    }
+
+    mem.values().copied().sum::<u64>()
+}
+
+/// A line of the initialization program.
+pub enum Instruction {
+    /// `mask = <mask>`.
+    SetMask(Bitmask),
+    /// `mem[<address>] = <value>`.
+    Memset(u64, u64),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(mask) = s.strip_prefix("mask = ") {
+            return Ok(Instruction::SetMask(parse_mask(s, mask)?));
+        }
+
+        lazy_static! {
+            static ref MEM_REGEX: Regex = Regex::new(r"^mem\[([0-9]+)\] = ([0-9]+)$").unwrap();
+        }
+
+        let matches = MEM_REGEX.captures(s).ok_or_else(|| {
+            ParseError::whole(s, "Expected 'mask = <mask>' or 'mem[<address>] = <value>'")
+        })?;
+        let addr = parse_field(s, matches.get(1).unwrap().as_str(), "address")?;
+        let value = parse_field(s, matches.get(2).unwrap().as_str(), "value")?;
+
+        Ok(Instruction::Memset(addr, value))
+    }
+}
+
+/// A 36 bit mask of `0`, `1` and `X`, from most to least significant bit.
+#[derive(Debug, Clone)]
+pub struct Bitmask {
+    ones: u64,
+    zeroes: u64,
+    floating: Vec<usize>, // Indices
+}
+
+impl Bitmask {
+    /// Masks a value as the first part does, overwriting the bits under a `0` or `1`.
+    pub fn apply(&self, value: u64) -> u64 {
+        (value & self.zeroes) | self.ones
+    }
+
+    /// Masks an address as the second part does. Bits under a `1` are set, and bits under an
+    /// `X` take every combination of values, giving 2^n addresses for n `X`s.
+    pub fn permute(&self, value: u64) -> Vec<u64> {
+        let base = value | self.ones;
+
+        fn generate_permutations(data: &mut Vec<u64>, remaining: &[usize], value: u64) {
+            if remaining.is_empty() {
+                data.push(value);
+                return;
+            }
+
+            let index = remaining[0];
+            generate_permutations(data, &remaining[1..], value | (1 << index));
+            generate_permutations(data, &remaining[1..], value & (u64::MAX - (1 << index)))
+        }
+
+        let mut values = Vec::new();
+        generate_permutations(&mut values, self.floating.as_slice(), base);
+        values
+    }
+}
+
+impl Default for Bitmask {
+    fn default() -> Self {
+        Bitmask {
+            ones: 0,
+            zeroes: u64::MAX,
+            floating: Vec::new(),
+        }
+    }
+}
+
+impl FromStr for Bitmask {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_mask(s, s)
+    }
+}
+
+const MASK_LENGTH: usize = 36;
+
+/// Parses `mask`, a slice of `line`, so that errors point into the whole line.
+fn parse_mask(line: &str, mask: &str) -> Result<Bitmask, ParseError> {
+    if let Some((position, c)) = mask.char_indices().find(|(_, c)| !"01X".contains(*c)) {
+        let start = mask.as_ptr() as usize - line.as_ptr() as usize + position;
+        return Err(ParseError::new(
+            line,
+            start..start + c.len_utf8(),
+            format!("Unexpected '{}' in mask", c),
+        ));
+    }
+
+    if mask.len() != MASK_LENGTH {
+        return Err(ParseError::at(
+            line,
+            mask,
+            format!("Expected a mask of {} bits", MASK_LENGTH),
+        ));
+    }
+
+    let mut ones: u64 = 0;
+    let mut zeroes = u64::MAX;
+    let mut floating = Vec::new();
+
+    for (index, c) in mask.chars().rev().enumerate() {
+        match c {
+            '1' => ones |= 1 << index,
+            '0' => zeroes &= u64::MAX - (1 << index),
+            _ => floating.push(index),
+        }
+    }
+
+    Ok(Bitmask {
+        ones,
+        zeroes,
+        floating,
+    })
+}
+
+/// Masks with at most nine floating bits, like the real input, each followed by a few writes.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut program = Vec::new();
+
+    while program.len() < size.max(2) {
+        let mut mask = (0..36)
+            .map(|_| if rng.chance(0.5) { '1' } else { '0' })
+            .collect::<Vec<char>>();
+        for _ in 0..rng.range(0..10) {
+            mask[rng.range(0..36)] = 'X';
+        }
+
+        program.push(format!("mask = {}", mask.into_iter().collect::<String>()));
+
+        for _ in 0..rng.range(1..6) {
+            program.push(format!(
+                "mem[{}] = {}",
+                rng.range(0..1 << 16),
+                rng.range(0..1 << 30)
+            ));
+        }
+    }
+
+    lines(program)
+}
+
+// Part 2 of the first example has too many floating bits to run, so each example only checks
+// the part it was written for.
+examples! {
+    Day14;
+
+    value_mask {
+        input: "\
+            mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\n\
+            mem[8] = 11\n\
+            mem[7] = 101\n\
+            mem[8] = 0",
+        part1: 165,
+    }
+
+    address_mask {
+        input: "\
+            mask = 000000000000000000000000000000X1001X\n\
+            mem[42] = 100\n\
+            mask = 00000000000000000000000000000000X0XX\n\
+            mem[26] = 1",
+        part2: 208,
+    }
+}
@@ -0,0 +1,174 @@
+//! Day 15: Rambunctious Recitation. Plays the elves' memory game.
+
+use std::collections::HashMap;
+
+use crate::{
+    generate::{lines, Rng},
+    input_lines, parse_field,
+    solution::Solution,
+};
+use anyhow::{Context, Result};
+
+/// The day 15 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 15;
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse() -> Result<Self::Input> {
+        let line = input_lines(Self::YEAR, Self::DAY)?
+            .next()
+            .context("The input is empty")??;
+
+        let numbers = line
+            .trim()
+            .split(',')
+            .map(|number| parse_field(&line, number, "starting number"))
+            .collect::<Result<Vec<usize>, _>>()
+            .map_err(|e| e.with_line(1))?;
+
+        Ok(numbers)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(part2(input))
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Last number: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Last number: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(seed: &[usize]) -> usize {
+    let mut game = MemoryGame::from_seed(seed);
+    game.run_until(2020);
+
+    game.last_number
+}
+
+fn part2(seed: &[usize]) -> usize {
+    let mut game = MemoryGame::from_seed(seed);
+    game.run_until(30000000);
+
+    game.last_number
+}
+
+/// The memory game, where each turn's number is how many turns ago the previous number was last
+/// spoken, or 0 if it hadn't been.
+pub struct MemoryGame {
+    occurences: HashMap<usize, usize>,
+    current_turn: usize,
+    /// The number spoken on the current turn.
+    pub last_number: usize,
+}
+
+impl MemoryGame {
+    /// A game where the starting numbers in `seed` have just been spoken.
+    ///
+    /// Panics if `seed` is empty.
+    pub fn from_seed(seed: &[usize]) -> MemoryGame {
+        MemoryGame {
+            // We want to insert everything except for the last element which is stored in the last_number element.
+            occurences: seed[0..seed.len() - 1]
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (*value, index + 1))
+                .collect(),
+            current_turn: seed.len(),
+            last_number: seed[seed.len() - 1],
+        }
+    }
+
+    /// The turn that `last_number` was spoken on, counting from 1.
+    pub fn turn(&self) -> usize {
+        self.current_turn
+    }
+
+    /// Plays until `turn` has been spoken. Nothing happens if that turn has already passed.
+    pub fn run_until(&mut self, turn: usize) {
+        while self.current_turn < turn {
+            self.step();
+        }
+    }
+
+    fn step(&mut self) {
+        let next_number = match self.occurences.get(&self.last_number) {
+            Some(last_turn) => self.current_turn - last_turn,
+            None => 0,
+        };
+
+        self.occurences.insert(self.last_number, self.current_turn);
+        self.current_turn += 1;
+        self.last_number = next_number;
+    }
+}
+
+/// `size` distinct starting numbers.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut numbers = (0..size * 3).collect::<Vec<usize>>();
+    rng.shuffle(&mut numbers);
+
+    let numbers = numbers[..size]
+        .iter()
+        .map(|number| number.to_string())
+        .collect::<Vec<String>>();
+
+    lines([numbers.join(",")])
+}
+
+// Part 2's examples play 30 million turns each, which is too slow for an unoptimised test build.
+examples! {
+    Day15;
+
+    seed_0_3_6 {
+        input: "0,3,6",
+        part1: 436,
+    }
+
+    seed_1_3_2 {
+        input: "1,3,2",
+        part1: 1,
+    }
+
+    seed_2_1_3 {
+        input: "2,1,3",
+        part1: 10,
+    }
+
+    seed_1_2_3 {
+        input: "1,2,3",
+        part1: 27,
+    }
+
+    seed_2_3_1 {
+        input: "2,3,1",
+        part1: 78,
+    }
+
+    seed_3_2_1 {
+        input: "3,2,1",
+        part1: 438,
+    }
+
+    seed_3_1_2 {
+        input: "3,1,2",
+        part1: 1836,
+    }
+}
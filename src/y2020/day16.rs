@@ -0,0 +1,345 @@
+//! Day 16: Ticket Translation. Works out which ticket field is which.
+
+use std::{collections::HashMap, str::FromStr};
+
+use crate::{
+    generate::{lines, Rng},
+    input_records, parse_field,
+    solution::Solution,
+    ParseError, Record,
+};
+use anyhow::{Context, Result};
+use log::trace;
+
+/// The day 16 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 16;
+    type Input = (Vec<Constraint>, Ticket, Vec<Ticket>);
+    type Answer1 = u32;
+    type Answer2 = u64;
+
+    fn parse() -> Result<Self::Input> {
+        let mut records = input_records(Self::YEAR, Self::DAY)?;
+
+        let constraints = records
+            .next()
+            .context("Missing the field rules")?
+            .context("Failed to read record")?;
+        let constraints = parse_lines(&constraints, 0)?;
+
+        // Both ticket sections start with a header line, e.g. "your ticket:".
+        let my_ticket = records
+            .next()
+            .context("Missing your ticket")?
+            .context("Failed to read record")?;
+        let my_ticket = parse_lines(&my_ticket, 1)?
+            .into_iter()
+            .next()
+            .context("Your ticket section is empty")?;
+
+        let other_tickets = records
+            .next()
+            .context("Missing the nearby tickets")?
+            .context("Failed to read record")?;
+        let other_tickets = parse_lines(&other_tickets, 1)?;
+
+        Ok((constraints, my_ticket, other_tickets))
+    }
+
+    fn part1((constraints, _, other_tickets): &Self::Input) -> Result<Self::Answer1> {
+        Ok(part1(constraints, other_tickets))
+    }
+
+    fn part2((constraints, my_ticket, other_tickets): &Self::Input) -> Result<Self::Answer2> {
+        part2(constraints, my_ticket, other_tickets)
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Error rate: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Product on my ticket: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+fn part1(constraints: &[Constraint], other_tickets: &[Ticket]) -> u32 {
+    other_tickets
+        .iter()
+        .filter_map(|ticket| ticket.find_invalid_field(constraints))
+        .sum::<u32>()
+}
+
+fn part2(constraints: &[Constraint], my_ticket: &Ticket, other_tickets: &[Ticket]) -> Result<u64> {
+    let valid_tickets = other_tickets
+        .iter()
+        .filter(|ticket| ticket.find_invalid_field(constraints).is_none())
+        .collect::<Vec<&Ticket>>();
+
+    // Find possible matches.
+    let mut possible_matches = constraints
+        .iter()
+        .map(|c| {
+            (
+                c.clone(),
+                (0..my_ticket.numbers().len())
+                    .filter(|i| {
+                        valid_tickets.iter().all(|ticket| {
+                            ticket
+                                .numbers()
+                                .get(*i)
+                                .is_some_and(|number| c.matches(*number))
+                        })
+                    })
+                    .collect(),
+            )
+        })
+        .collect::<HashMap<Constraint, Vec<usize>>>();
+
+    // Go through and find the element with only a single possibility.
+    let mut matches: HashMap<Constraint, usize> = HashMap::new();
+
+    while !possible_matches.is_empty() {
+        let constraint = possible_matches
+            .iter()
+            .filter(|(_, indicies)| indicies.len() == 1)
+            .map(|(c, _)| c.clone())
+            .next()
+            .context("The fields can't be told apart one at a time")?;
+
+        let index = possible_matches.remove(&constraint).unwrap()[0];
+
+        for indices in possible_matches.values_mut() {
+            indices.retain(|val| *val != index);
+        }
+
+        trace!("Field '{}' is column {}", constraint.name, index);
+        matches.insert(constraint, index);
+    }
+
+    Ok(matches
+        .iter()
+        .filter(|(c, _)| c.name.starts_with("departure"))
+        .map(|(_, index)| my_ticket.number(*index) as u64)
+        .product::<u64>())
+}
+
+/// A rule for a ticket field, like `class: 1-3 or 5-7`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Constraint {
+    pub name: String,
+    ranges: Vec<(u32, u32)>,
+}
+
+/// Parses each line of `record` after the first `skip`, numbering errors by their input line.
+fn parse_lines<T>(record: &Record, skip: usize) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    record
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(index, line)| {
+            line.parse::<T>()
+                .map_err(|e| e.with_line(record.line_number(index)))
+        })
+        .collect()
+}
+
+impl Constraint {
+    /// Whether `number` is in any of the rule's inclusive ranges.
+    pub fn matches(&self, number: u32) -> bool {
+        self.ranges
+            .iter()
+            .any(|(lower, upper)| number >= *lower && number <= *upper)
+    }
+}
+
+impl FromStr for Constraint {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, ranges) = s
+            .split_once(": ")
+            .ok_or_else(|| ParseError::whole(s, "Expected a rule like 'class: 1-3 or 5-7'"))?;
+
+        let ranges = ranges
+            .split(" or ")
+            .map(|slice| {
+                let (lower, upper) = slice
+                    .split_once('-')
+                    .ok_or_else(|| ParseError::at(s, slice, "Expected a range like '1-3'"))?;
+
+                Ok((
+                    parse_field(s, lower, "number")?,
+                    parse_field(s, upper, "number")?,
+                ))
+            })
+            .collect::<Result<Vec<(u32, u32)>, ParseError>>()?;
+
+        Ok(Constraint {
+            name: name.to_owned(),
+            ranges,
+        })
+    }
+}
+
+/// A ticket's field values in order, parsed from a line like `7,1,14`.
+pub struct Ticket {
+    numbers: Vec<u32>,
+}
+
+impl Ticket {
+    pub fn numbers(&self) -> &[u32] {
+        &self.numbers
+    }
+
+    /// The first value that no rule in `constraints` accepts, if there is one.
+    pub fn find_invalid_field(&self, constraints: &[Constraint]) -> Option<u32> {
+        self.numbers
+            .iter()
+            .filter(|num| constraints.iter().all(|c| !c.matches(**num)))
+            .cloned()
+            .nth(0)
+    }
+
+    /// The value at `index`, which must be in range.
+    pub fn number(&self, index: usize) -> u32 {
+        self.numbers[index]
+    }
+}
+
+impl FromStr for Ticket {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Ticket {
+            numbers: s
+                .split(",")
+                .map(|number| parse_field(s, number, "number"))
+                .collect::<Result<Vec<u32>, ParseError>>()?,
+        })
+    }
+}
+
+/// Rules for twenty fields and `size` nearby tickets, some of them invalid.
+///
+/// Each field accepts a shared band of values, plus everything from its own threshold up. Each
+/// field's column holds its threshold on one ticket, so the columns fit one field, two fields,
+/// three and so on, and part 2 can pin down the fields one at a time.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const FIELDS: [&str; 20] = [
+        "departure location",
+        "departure station",
+        "departure platform",
+        "departure track",
+        "departure date",
+        "departure time",
+        "arrival location",
+        "arrival station",
+        "arrival platform",
+        "arrival track",
+        "class",
+        "duration",
+        "price",
+        "route",
+        "row",
+        "seat",
+        "train",
+        "type",
+        "wagon",
+        "zone",
+    ];
+
+    // Fields in order of increasing threshold, and the column each one is in.
+    let mut names = FIELDS.to_vec();
+    rng.shuffle(&mut names);
+    let mut columns = (0..FIELDS.len()).collect::<Vec<usize>>();
+    rng.shuffle(&mut columns);
+
+    let thresholds = (0..FIELDS.len())
+        .map(|rank| 400 + 25 * rank + rng.range(0..25))
+        .collect::<Vec<usize>>();
+
+    let mut rules = (0..FIELDS.len())
+        .map(|rank| {
+            format!(
+                "{}: {}-{} or {}-{}",
+                names[rank],
+                rng.range(25..50),
+                rng.range(300..400),
+                thresholds[rank],
+                rng.range(900..1000)
+            )
+        })
+        .collect::<Vec<String>>();
+    rng.shuffle(&mut rules);
+
+    let ticket = |rng: &mut Rng| {
+        (0..FIELDS.len())
+            .map(|_| rng.range(50..300))
+            .collect::<Vec<usize>>()
+    };
+
+    let mine = ticket(rng);
+    let mut nearby = (0..size.max(1)).map(|_| ticket(rng)).collect::<Vec<_>>();
+    let mut marked = vec![false; nearby.len()];
+
+    for rank in 0..FIELDS.len() {
+        let index = rng.range(0..nearby.len());
+        nearby[index][columns[rank]] = thresholds[rank];
+        marked[index] = true;
+    }
+
+    // Values below every field's band are invalid for all of them.
+    for (ticket, marked) in nearby.iter_mut().zip(marked) {
+        if !marked && rng.chance(0.25) {
+            ticket[rng.range(0..FIELDS.len())] = rng.range(1..25);
+        }
+    }
+
+    let format = |ticket: &[usize]| {
+        ticket
+            .iter()
+            .map(|number| number.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+
+    let mut input = rules;
+    input.extend(["".to_owned(), "your ticket:".to_owned(), format(&mine)]);
+    input.extend(["".to_owned(), "nearby tickets:".to_owned()]);
+    input.extend(nearby.iter().map(|ticket| format(ticket)));
+
+    lines(input)
+}
+
+// Part 2 multiplies the puzzle input's six `departure` fields, which the example doesn't have.
+examples! {
+    Day16;
+
+    ticket_fields {
+        input: "\
+            class: 1-3 or 5-7\n\
+            row: 6-11 or 33-44\n\
+            seat: 13-40 or 45-50\n\
+            \n\
+            your ticket:\n\
+            7,1,14\n\
+            \n\
+            nearby tickets:\n\
+            7,3,47\n\
+            40,4,50\n\
+            55,2,20\n\
+            38,6,12",
+        part1: 71,
+    }
+}
@@ -0,0 +1,310 @@
+//! Day 17: Conway Cubes. Runs Conway's Game of Life in three and four dimensions.
+
+use crate::{
+    generate::{lines, Rng},
+    input_grid,
+    solution::Solution,
+    Grid,
+};
+use anyhow::Result;
+use lazy_static::lazy_static;
+
+/// The day 17 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 17;
+    type Input = PocketDimension;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse() -> Result<Self::Input> {
+        let slice = input_grid(Self::YEAR, Self::DAY, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+
+        Ok(PocketDimension::from(slice))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        let mut p1_input = input.clone();
+
+        for _ in 0..6 {
+            p1_input.cycle(false);
+        }
+
+        Ok(p1_input.count_active())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        let mut p2_input = input.clone();
+
+        for _ in 0..6 {
+            p2_input.cycle(true);
+        }
+
+        Ok(p2_input.count_active())
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Part 1 active: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Part 2 active: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+/// The active cubes of a pocket dimension, which grows by one cube in each direction every cycle.
+/// Build one from a starting slice with `From<Grid<bool>>`, where `true` is active.
+#[derive(Clone)]
+pub struct PocketDimension {
+    bounds: (usize, usize, usize, usize), // x, y, z, w
+    data: Vec<Vec<Vec<Vec<bool>>>>,       // w, z, y, x
+}
+
+lazy_static! {
+    static ref ADJ3: Vec<(isize, isize, isize, isize)> = generate_adj3();
+    static ref ADJ4: Vec<(isize, isize, isize, isize)> = generate_adj4();
+}
+
+impl PocketDimension {
+    /// How many cubes are active.
+    pub fn count_active(&self) -> usize {
+        self.data
+            .iter()
+            .map(|slice_3d| {
+                slice_3d
+                    .iter()
+                    .map(|slice_2d| {
+                        slice_2d
+                            .iter()
+                            .map(|row| row.iter().filter(|cube| **cube).count())
+                            .sum::<usize>()
+                    })
+                    .sum::<usize>()
+            })
+            .sum()
+    }
+
+    /// Runs one cycle of the rules over three dimensions, or four if `fourth_dimension` is set.
+    /// An active cube stays active with 2 or 3 active neighbours, and an inactive one becomes
+    /// active with exactly 3.
+    pub fn cycle(&mut self, fourth_dimension: bool) {
+        self.expand(fourth_dimension);
+
+        let adj = if fourth_dimension { &*ADJ4 } else { &*ADJ3 };
+
+        let mut new_data = self.data.clone();
+
+        #[allow(clippy::needless_range_loop)]
+        for w in 0..self.bounds.3 {
+            for z in 0..self.bounds.2 {
+                for y in 0..self.bounds.1 {
+                    for x in 0..self.bounds.0 {
+                        let neighbors = self.count_neighbors(x, y, z, w, adj);
+                        new_data[w][z][y][x] = match self.data[w][z][y][x] {
+                            true => neighbors == 2 || neighbors == 3,
+                            false => neighbors == 3,
+                        }
+                    }
+                }
+            }
+        }
+
+        self.data = new_data;
+    }
+
+    fn count_neighbors(
+        &self,
+        x: usize,
+        y: usize,
+        z: usize,
+        w: usize,
+        adj: &Vec<(isize, isize, isize, isize)>,
+    ) -> u32 {
+        let mut count = 0;
+        for adj in adj {
+            let x = adj.0 + x as isize;
+            let y = adj.1 + y as isize;
+            let z = adj.2 + z as isize;
+            let w = adj.3 + w as isize;
+
+            if x < 0 || x >= self.bounds.0 as isize {
+                continue;
+            }
+
+            if y < 0 || y >= self.bounds.1 as isize {
+                continue;
+            }
+
+            if z < 0 || z >= self.bounds.2 as isize {
+                continue;
+            }
+
+            if w < 0 || w >= self.bounds.3 as isize {
+                continue;
+            }
+
+            if self.data[w as usize][z as usize][y as usize][x as usize] {
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    fn expand(&mut self, fourth_dimension: bool) {
+        // Increase the bounds by one one either side.
+        if fourth_dimension {
+            self.bounds = (
+                self.bounds.0 + 2,
+                self.bounds.1 + 2,
+                self.bounds.2 + 2,
+                self.bounds.3 + 2,
+            );
+        } else {
+            self.bounds = (
+                self.bounds.0 + 2,
+                self.bounds.1 + 2,
+                self.bounds.2 + 2,
+                self.bounds.3,
+            );
+        }
+
+        // Generate the sequences for empty values.
+        let row = std::iter::repeat_n(false, self.bounds.0).collect::<Vec<bool>>();
+        let slice_2d = std::iter::repeat_n(row.clone(), self.bounds.1).collect::<Vec<Vec<bool>>>();
+
+        // Expand the innermost elements.
+        for slice_3d in &mut self.data {
+            for slice_2d in slice_3d.iter_mut() {
+                for r in slice_2d.iter_mut() {
+                    r.insert(0, false);
+                    r.push(false);
+                }
+
+                // Make sure to push an extra row.
+                slice_2d.push(row.clone());
+                slice_2d.insert(0, row.clone())
+            }
+
+            slice_3d.push(slice_2d.clone());
+            slice_3d.insert(0, slice_2d.clone());
+        }
+
+        if fourth_dimension {
+            let slice_3d = std::iter::repeat_n(slice_2d.clone(), self.bounds.2)
+                .collect::<Vec<Vec<Vec<bool>>>>();
+
+            self.data.push(slice_3d.clone());
+            self.data.insert(0, slice_3d);
+        }
+    }
+}
+
+impl From<Grid<bool>> for PocketDimension {
+    fn from(slice: Grid<bool>) -> Self {
+        let data = slice
+            .rows()
+            .map(|row| row.to_vec())
+            .collect::<Vec<Vec<bool>>>();
+
+        PocketDimension {
+            bounds: (slice.width(), slice.height(), 1, 1),
+            data: vec![vec![data]],
+        }
+    }
+}
+
+// impl Display for PocketDimension {
+//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//         for slice in &self.data {
+//             for row in slice {
+//                 for item in row {
+//                     let c = match item {
+//                         true => "#",
+//                         false => ".",
+//                     };
+
+//                     f.write_str(c)?;
+//                 }
+
+//                 f.write_str("\n")?;
+//             }
+
+//             f.write_str("\n")?;
+//         }
+
+//         Ok(())
+//     }
+// }
+
+fn generate_adj3() -> Vec<(isize, isize, isize, isize)> {
+    let mut adj = Vec::new();
+
+    for x in -1..=1 {
+        for y in -1..=1 {
+            for z in -1..=1 {
+                if x == 0 && y == 0 && z == 0 {
+                    continue;
+                }
+
+                adj.push((x, y, z, 0))
+            }
+        }
+    }
+
+    adj
+}
+
+fn generate_adj4() -> Vec<(isize, isize, isize, isize)> {
+    let mut adj = Vec::new();
+
+    for x in -1..=1 {
+        for y in -1..=1 {
+            for z in -1..=1 {
+                for w in -1..=1 {
+                    if x == 0 && y == 0 && z == 0 && w == 0 {
+                        continue;
+                    }
+
+                    adj.push((x, y, z, w))
+                }
+            }
+        }
+    }
+
+    adj
+}
+
+/// A square starting slice `size` cubes across.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+
+    lines((0..size).map(|_| {
+        (0..size)
+            .map(|_| if rng.chance(0.4) { '#' } else { '.' })
+            .collect::<String>()
+    }))
+}
+
+examples! {
+    Day17;
+
+    pocket_dimension {
+        input: "\
+            .#.\n\
+            ..#\n\
+            ###",
+        part1: 112,
+        part2: 848,
+    }
+}
@@ -0,0 +1,327 @@
+//! Day 18: Operation Order. Evaluates homework with unusual operator precedence.
+
+use std::{collections::HashMap, str::FromStr};
+
+use crate::{
+    generate::{lines, Rng},
+    input_parsed, parse_field,
+    solution::Solution,
+    ParseError,
+};
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// The day 18 puzzle, registered in [`SOLUTIONS`](crate::solution::SOLUTIONS).
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 18;
+    type Input = Vec<Expression>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse() -> Result<Self::Input> {
+        input_parsed(Self::YEAR, Self::DAY)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+        Ok(input
+            .iter()
+            .map(Expression::eval_simple)
+            .collect::<Result<Vec<u64>>>()?
+            .iter()
+            .sum::<u64>())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+        Ok(input
+            .iter()
+            .map(Expression::eval_advanced)
+            .collect::<Result<Vec<u64>>>()?
+            .iter()
+            .sum::<u64>())
+    }
+
+    fn describe1(answer: &Self::Answer1) -> String {
+        format!("Sum of results, simple: {}", answer)
+    }
+
+    fn describe2(answer: &Self::Answer2) -> String {
+        format!("Sum of results, advanced: {}", answer)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Result<String> {
+        Ok(generate(rng, size))
+    }
+}
+
+/// A line of homework, checked to be numbers and operators separated by single spaces, with
+/// balanced parentheses.
+pub struct Expression {
+    text: String,
+}
+
+impl Expression {
+    /// Evaluates the expression with `+` and `*` at the same precedence, left to right.
+    pub fn eval_simple(&self) -> Result<u64> {
+        eval(self.text.clone(), eval_expr_p1)
+    }
+
+    /// Evaluates the expression with `+` binding tighter than `*`.
+    pub fn eval_advanced(&self) -> Result<u64> {
+        eval(self.text.clone(), eval_expr_p2)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut open = Vec::new();
+        let mut expect_operand = true;
+        let mut chars = s.char_indices().peekable();
+
+        while let Some((index, c)) = chars.next() {
+            let span = index..index + c.len_utf8();
+            let prev = s[..index].chars().last();
+            let next = chars.peek().map(|(_, c)| *c);
+
+            match c {
+                '0'..='9' if expect_operand => {
+                    let mut end = index + 1;
+                    while let Some((_, '0'..='9')) = chars.peek() {
+                        end += 1;
+                        chars.next();
+                    }
+
+                    parse_field::<u64>(s, &s[index..end], "number")?;
+                    expect_operand = false;
+                }
+                '(' if expect_operand => open.push(index),
+                ')' if !expect_operand => {
+                    if open.pop().is_none() {
+                        return Err(ParseError::new(s, span, "Unmatched ')'"));
+                    }
+                }
+                '+' | '*' if !expect_operand => {
+                    if prev != Some(' ') || next != Some(' ') {
+                        return Err(ParseError::new(
+                            s,
+                            span,
+                            "Expected a space either side of the operator",
+                        ));
+                    }
+                    expect_operand = true;
+                }
+                ' ' if matches!(prev, Some('+' | '*')) || matches!(next, Some('+' | '*')) => {}
+                ' ' => return Err(ParseError::new(s, span, "Unexpected space")),
+                '0'..='9' | '(' => return Err(ParseError::new(s, span, "Expected an operator")),
+                ')' | '+' | '*' => return Err(ParseError::new(s, span, "Expected a number")),
+                _ => return Err(ParseError::new(s, span, format!("Unexpected '{}'", c))),
+            }
+        }
+
+        if expect_operand {
+            return Err(ParseError::end(s, "Expected a number"));
+        }
+
+        if let Some(index) = open.pop() {
+            return Err(ParseError::new(s, index..index + 1, "Unmatched '('"));
+        }
+
+        Ok(Expression { text: s.to_owned() })
+    }
+}
+
+fn eval<F>(mut expr: String, eval_expr: F) -> Result<u64>
+where
+    F: Fn(&str) -> Result<u64>,
+{
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"\([^\(\)]*\)").unwrap();
+    }
+
+    while RE.is_match(&expr) {
+        let mut matches = HashMap::new();
+        for caps in RE.captures_iter(&expr) {
+            let expr = &caps[0];
+            let result = eval_expr(&expr[1..expr.len() - 1])?;
+            matches.insert(expr.to_owned(), result);
+        }
+
+        for (sub_expr, value) in matches {
+            expr = expr.replace(&sub_expr, &format!("{}", value));
+        }
+    }
+
+    eval_expr(&expr)
+}
+
+/// Evaluate an expression which does not contain any parenthesis.
+fn eval_expr_p1(expr: &str) -> Result<u64> {
+    if expr.contains(")") || expr.contains("(") {
+        return Err(anyhow::anyhow!("expression contained parenthesis"));
+    }
+
+    let mut iter = expr.split(" ");
+
+    // Get the initial value.
+    let mut value = iter
+        .next()
+        .unwrap()
+        .parse::<u64>()
+        .context("Failed to parse character as number")?;
+
+    let mut operator: Option<Operator> = None;
+
+    for c in iter {
+        if let Some(operator) = operator.take() {
+            let operand = c
+                .parse::<u64>()
+                .context("Failed to parse string as number")?;
+
+            match operator {
+                Operator::Plus => value += operand,
+                Operator::Times => value *= operand,
+            }
+
+            continue;
+        }
+
+        operator = Some(match c {
+            "*" => Operator::Times,
+            "+" => Operator::Plus,
+            _ => anyhow::bail!("Unknown operator: {}", c),
+        });
+
+        continue;
+    }
+
+    Ok(value)
+}
+
+/// Evaluate an expression which does not contain any parenthesis.
+fn eval_expr_p2(expr: &str) -> Result<u64> {
+    // Need to do addition first, then multiplication.
+    if expr.contains(")") || expr.contains("(") {
+        return Err(anyhow::anyhow!("expression contained parenthesis"));
+    }
+
+    let mut parts = expr
+        .split(" ")
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>();
+
+    // Find the indices of addition signs.
+    let addition_indices = parts
+        .iter()
+        .enumerate()
+        .filter_map(|(index, pat)| if *pat == "+" { Some(index) } else { None })
+        .collect::<Vec<usize>>();
+
+    let mut offset = 0;
+
+    // For each addition sign, compute the addition and replace the expression with the result.
+    // Must take care with indexing as we are mutating in place.
+    for indice in addition_indices {
+        let value = parts[indice - 1 - offset].parse::<u64>()?
+            + parts[indice + 1 - offset].parse::<u64>()?;
+        parts[indice - offset] = format!("{}", value);
+        parts.remove(indice + 1 - offset);
+        parts.remove(indice - 1 - offset);
+
+        offset += 2;
+    }
+
+    // Now find all the remaining numbers and multiply them together.
+    Ok(parts
+        .iter()
+        .filter_map(|pat| {
+            if pat != "*" {
+                Some(pat.parse::<u64>().context("Failed to parse number"))
+            } else {
+                None
+            }
+        })
+        .collect::<Result<Vec<u64>>>()?
+        .iter()
+        .product::<u64>())
+}
+
+enum Operator {
+    Plus,
+    Times,
+}
+
+/// Homework of single digits, nested at most two deep. Each line has only a dozen or so digits so
+/// that both parts' results fit in 64 bits.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    fn expression(rng: &mut Rng, depth: usize, digits: &mut usize) -> String {
+        let mut text = String::new();
+
+        for term in 0..rng.range(2..5) {
+            if term > 0 {
+                text.push_str(if rng.chance(0.5) { " + " } else { " * " });
+            }
+
+            if depth < 2 && *digits >= 4 && rng.chance(0.3) {
+                text.push('(');
+                text.push_str(&expression(rng, depth + 1, digits));
+                text.push(')');
+            } else {
+                *digits = digits.saturating_sub(1);
+                text.push_str(&rng.range(1..10).to_string());
+            }
+        }
+
+        text
+    }
+
+    lines((0..size).map(|_| expression(rng, 0, &mut 10)))
+}
+
+examples! {
+    Day18;
+
+    no_parentheses {
+        input: "1 + 2 * 3 + 4 * 5 + 6",
+        part1: 71,
+        part2: 231,
+    }
+
+    parentheses {
+        input: "1 + (2 * 3) + (4 * (5 + 6))",
+        part1: 51,
+        part2: 51,
+    }
+
+    trailing_group {
+        input: "2 * 3 + (4 * 5)",
+        part1: 26,
+        part2: 46,
+    }
+
+    nested_group {
+        input: "5 + (8 * 3 + 9 + 3 * 4 * 3)",
+        part1: 437,
+        part2: 1445,
+    }
+
+    deep_nesting {
+        input: "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))",
+        part1: 12240,
+        part2: 669060,
+    }
+
+    leading_groups {
+        input: "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+        part1: 13632,
+        part2: 23340,
+    }
+}